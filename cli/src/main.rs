@@ -1,12 +1,19 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow, bail};
+use chrono::Utc;
 use git_testament::git_testament;
 use tokio::join;
 
-use github_contrib_stats::github::{ContributedRepo, Repository};
-use github_contrib_stats::render::SvgRenderer;
-use github_contrib_stats::{github, render::MarkdownRenderer, render::Render};
+use github_contrib_stats::github::{
+    self, Affiliation, ContributedRepo, ContributedReposOptions, ContributionKind,
+    CreatedReposOptions, DateRange, GitHubClient, OwnerKind, RepoContributions, Repository,
+};
+use github_contrib_stats::query::{RepoFilter, RepoOrder, RepoPattern, StatsQuery};
+use github_contrib_stats::render::{Column, PrivateRepos, SvgRenderer};
+use github_contrib_stats::stats::{ContributionStats, LanguageWeight};
+use github_contrib_stats::theme::Theme;
+use github_contrib_stats::{render::MarkdownRenderer, render::Render};
 
 git_testament!(TESTAMENT);
 
@@ -22,9 +29,127 @@ async fn main() -> Result<()> {
             clap::Arg::new("username")
                 .short('u')
                 .long("username")
-                .help("GitHub username")
+                .help("GitHub username or organization login")
                 .required(true),
         )
+        .arg(
+            clap::Arg::new("owner")
+                .long("owner")
+                .value_name("KIND")
+                .value_parser(clap::value_parser!(OwnerKind))
+                .default_value("auto")
+                .help("Account kind of --username: auto, user or organization"),
+        )
+        .arg(
+            clap::Arg::new("affiliation")
+                .long("affiliation")
+                .value_name("AFFILIATION")
+                .value_parser(clap::value_parser!(Affiliation))
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .default_value("owner")
+                .help("List repos where the user is an owner, collaborator or organization_member"),
+        )
+        .arg(
+            clap::Arg::new("include-forks")
+                .long("include-forks")
+                .action(clap::ArgAction::SetTrue)
+                .help("Include forked repositories"),
+        )
+        .arg(
+            clap::Arg::new("exclude-archived")
+                .long("exclude-archived")
+                .action(clap::ArgAction::SetTrue)
+                .help("Exclude archived repositories"),
+        )
+        .arg(
+            clap::Arg::new("include-unstarred")
+                .long("include-unstarred")
+                .action(clap::ArgAction::SetTrue)
+                .help("Include repositories without stars or forks"),
+        )
+        .arg(
+            clap::Arg::new("include-private")
+                .long("include-private")
+                .action(clap::ArgAction::SetTrue)
+                .help("Include private repositories and contributions the token can see"),
+        )
+        .arg(
+            clap::Arg::new("include")
+                .long("include")
+                .value_name("PATTERN")
+                .value_parser(clap::value_parser!(RepoPattern))
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .env("GITHUB_CONTRIB_STATS_INCLUDE")
+                .help("Only keep repos matching owner/name or owner globs, or lang:LANGUAGE"),
+        )
+        .arg(
+            clap::Arg::new("exclude")
+                .long("exclude")
+                .value_name("PATTERN")
+                .value_parser(clap::value_parser!(RepoPattern))
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .env("GITHUB_CONTRIB_STATS_EXCLUDE")
+                .help("Drop repos matching owner/name or owner globs, or lang:LANGUAGE"),
+        )
+        .arg(
+            clap::Arg::new("sort")
+                .long("sort")
+                .value_name("KEY[:asc|desc]")
+                .value_parser(clap::value_parser!(RepoOrder))
+                .help("Sort repos by stars, forks, updated, created, prs or name"),
+        )
+        .arg(
+            clap::Arg::new("private-repos")
+                .long("private-repos")
+                .value_name("MODE")
+                .value_parser(clap::value_parser!(PrivateRepos))
+                .default_value("show")
+                .help("Show private repositories by name, or anonymize them into one row"),
+        )
+        .arg(
+            clap::Arg::new("since")
+                .long("since")
+                .value_name("DATE")
+                .help("Only count activity from DATE on: YYYY-MM-DD, YYYY, or 30d, 2w, 6m, 1y ago"),
+        )
+        .arg(
+            clap::Arg::new("until")
+                .long("until")
+                .value_name("DATE")
+                .help("Only count activity up to DATE, in the same forms as --since"),
+        )
+        .arg(
+            clap::Arg::new("token")
+                .long("token")
+                .env("GITHUB_TOKEN")
+                .hide_env_values(true)
+                .help("GitHub token used to query the API")
+                .required_unless_present("replay"),
+        )
+        .arg(
+            clap::Arg::new("api-url")
+                .long("api-url")
+                .env("GITHUB_API_URL")
+                .help("GitHub API root, e.g. https://HOSTNAME/api for GitHub Enterprise Server"),
+        )
+        .arg(
+            clap::Arg::new("record")
+                .long("record")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Save every GitHub API request and response to DIR"),
+        )
+        .arg(
+            clap::Arg::new("replay")
+                .long("replay")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with("record")
+                .help("Answer GitHub API requests from responses saved with --record"),
+        )
         .arg(
             clap::Arg::new("update")
                 .long("update")
@@ -39,29 +164,250 @@ async fn main() -> Result<()> {
                 .default_value("markdown")
                 .help("The output format"),
         )
+        .arg(
+            clap::Arg::new("theme")
+                .long("theme")
+                .value_name("THEME")
+                .value_parser(clap::value_parser!(Theme))
+                .default_value("light")
+                .help("Colours of the SVG cards: light, dark, dracula, solarized or high_contrast"),
+        )
+        .arg(
+            clap::Arg::new("dark-theme")
+                .long("dark-theme")
+                .value_name("THEME")
+                .value_parser(clap::value_parser!(Theme))
+                .help("Switch the SVG cards to THEME for viewers who prefer dark mode"),
+        )
+        .arg(
+            clap::Arg::new("width")
+                .long("width")
+                .value_name("PIXELS")
+                .value_parser(
+                    clap::value_parser!(u32).range(
+                        i64::from(SvgRenderer::MIN_WIDTH)..=i64::from(SvgRenderer::MAX_WIDTH),
+                    ),
+                )
+                .default_value("780")
+                .help("Width of the SVG cards"),
+        )
+        .arg(
+            clap::Arg::new("compact")
+                .long("compact")
+                .action(clap::ArgAction::SetTrue)
+                .help("Draw lower rows in the SVG cards, e.g. for a sidebar"),
+        )
         .arg(
             clap::Arg::new("max-repos")
                 .short('m')
                 .long("max-repos")
+                .value_parser(clap::value_parser!(usize))
                 .help("Maximum number of repositories to show"),
         )
+        .arg(
+            clap::Arg::new("min-stars")
+                .long("min-stars")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("0")
+                .help("Only show repos with at least COUNT stars"),
+        )
+        .arg(
+            clap::Arg::new("min-forks")
+                .long("min-forks")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("0")
+                .help("Only show repos with at least COUNT forks"),
+        )
+        .arg(
+            clap::Arg::new("min-prs")
+                .long("min-prs")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("0")
+                .help("Only show contributed repos with at least COUNT PRs"),
+        )
+        .arg(
+            clap::Arg::new("min-merged")
+                .long("min-merged")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(u32))
+                .default_value("0")
+                .help("Only show contributed repos with at least COUNT merged PRs"),
+        )
+        .arg(
+            clap::Arg::new("columns")
+                .long("columns")
+                .value_name("COLUMN")
+                .value_parser(clap::value_parser!(Column))
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("Columns of the repo tables, in order; each table shows those it has data for, see README.md"),
+        )
+        .arg(
+            clap::Arg::new("language-weight")
+                .long("language-weight")
+                .value_name("WEIGHT")
+                .value_parser(clap::value_parser!(LanguageWeight))
+                .default_value("repos")
+                .help("Weigh languages.svg by the number of repos or by their stars"),
+        )
+        .arg(
+            clap::Arg::new("timeline-repos")
+                .long("timeline-repos")
+                .value_name("COUNT")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("0")
+                .help("Stack the PRs of the COUNT repos with most PRs in timeline.svg"),
+        )
+        .arg(
+            clap::Arg::new("summary")
+                .long("summary")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print and add a summary of PRs per year, streaks and top languages"),
+        )
+        .arg(
+            clap::Arg::new("contribution-kinds")
+                .long("contribution-kinds")
+                .value_name("KIND")
+                .value_parser(clap::value_parser!(ContributionKind))
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("Also count pr, issue, review or discussion_answer contributions per repo"),
+        )
         .get_matches();
 
     let username = matches.get_one::<String>("username").unwrap();
-    let max_repos = matches.get_one::<usize>("max-repos").copied();
+    let mut client = GitHubClient::builder();
+    if let Some(token) = matches.get_one::<String>("token") {
+        client = client.token(token);
+    }
+    if let Some(api_url) = matches.get_one::<String>("api-url") {
+        client = client.base_url(api_url);
+    }
+    if let Some(dir) = matches.get_one::<PathBuf>("record") {
+        client = client.record(dir);
+    }
+    if let Some(dir) = matches.get_one::<PathBuf>("replay") {
+        client = client.replay(dir);
+    }
+    let client = client.build()?;
+
+    let range = DateRange::parse(
+        matches.get_one::<String>("since").map(String::as_str),
+        matches.get_one::<String>("until").map(String::as_str),
+    )
+    .map_err(|e| anyhow!(e))?;
+    let patterns = |name| {
+        matches
+            .get_many::<RepoPattern>(name)
+            .unwrap_or_default()
+            .cloned()
+            .collect()
+    };
+    let min = |name| *matches.get_one::<u32>(name).unwrap();
+    let query = StatsQuery {
+        filter: RepoFilter {
+            include: patterns("include"),
+            exclude: patterns("exclude"),
+        },
+        min_stars: min("min-stars"),
+        min_forks: min("min-forks"),
+        min_prs: min("min-prs"),
+        min_merged: min("min-merged"),
+        sort: matches.get_one::<RepoOrder>("sort").copied(),
+        max_repos: matches.get_one::<usize>("max-repos").copied(),
+    };
+    let created_options = CreatedReposOptions {
+        owner: *matches.get_one::<OwnerKind>("owner").unwrap(),
+        affiliations: matches
+            .get_many::<Affiliation>("affiliation")
+            .unwrap()
+            .copied()
+            .collect(),
+        include_forks: matches.get_flag("include-forks"),
+        include_archived: !matches.get_flag("exclude-archived"),
+        include_unstarred: matches.get_flag("include-unstarred"),
+        include_private: matches.get_flag("include-private"),
+        range,
+    };
+    let contributed_options = ContributedReposOptions {
+        range,
+        include_private: created_options.include_private,
+        include_archived: created_options.include_archived,
+    };
 
-    let (created_repos, contributed_repos) = join!(
-        github::get_created_repos(username, max_repos),
-        github::get_contributed_repos(username, max_repos),
+    let (created_repos, prs) = join!(
+        client.get_created_repos(username, &created_options),
+        client.get_pull_requests(username, &contributed_options),
+    );
+    let (created_repos, prs) = (created_repos.map_err(explain)?, prs.map_err(explain)?);
+    // The summary counts everything matching the patterns, not only the repos listed.
+    let summary = matches.get_flag("summary").then(|| {
+        let matching = query.filter_only();
+        let prs: Vec<_> = prs
+            .iter()
+            .filter(|pr| contributed_options.include_archived || !pr.repository.is_archived)
+            .cloned()
+            .collect();
+        ContributionStats::new(
+            &matching.apply(prs),
+            &matching.apply(created_repos.clone()),
+            Utc::now(),
+        )
+    });
+    // Like the summary, the timeline counts the PRs of all matching repos.
+    let timeline_repos = query
+        .filter_only()
+        .apply(github::group_pull_requests(&prs, &contributed_options));
+    let (created_repos, contributed_repos) = (
+        query.apply(created_repos),
+        query.apply(timeline_repos.clone()),
     );
-    let (created_repos, contributed_repos) = (created_repos?, contributed_repos?);
+    let private_repos = *matches.get_one::<PrivateRepos>("private-repos").unwrap();
+    let columns: Vec<Column> = matches
+        .get_many::<Column>("columns")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    let kinds: Vec<ContributionKind> = matches
+        .get_many::<ContributionKind>("contribution-kinds")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    let contributions = if kinds.is_empty() {
+        Vec::new()
+    } else {
+        let contributions = client
+            .get_contributions(username, &kinds, &contributed_options)
+            .await
+            .map_err(explain)?;
+        query.apply(contributions)
+    };
+
+    if let Some(summary) = &summary {
+        let mut buf = String::new();
+        MarkdownRenderer::new().render_summary(&mut buf, summary, username);
+        println!("{buf}");
+    }
 
     // Handle different output scenarios
     if let Some(update_file) = matches.get_one::<String>("update") {
         // Scenario 1: Update existing markdown file
         let path = Path::new(update_file);
         if path.exists() {
-            update_markdown(path, created_repos, contributed_repos, username)?;
+            let render = MarkdownRenderer::new()
+                .with_private_repos(private_repos)
+                .with_columns(columns.clone());
+            let sections = Sections {
+                created_repos: &created_repos,
+                contributed_repos: &contributed_repos,
+                contributions: &contributions,
+                kinds: &kinds,
+                summary: summary.as_ref(),
+            };
+            update_markdown(path, &render, &sections, username)?;
         } else {
             bail!("File {} does not exist", update_file);
         }
@@ -71,24 +417,65 @@ async fn main() -> Result<()> {
             "markdown" => {
                 // Scenario 2: Create new markdown file
                 let output = Path::new("github-contrib-stats.md");
-                let render = MarkdownRenderer::new();
+                let render = MarkdownRenderer::new()
+                    .with_private_repos(private_repos)
+                    .with_columns(columns.clone());
                 let mut buf =
                     String::from("# My GitHub Contribution Stats\n\n## Repos I Created\n\n");
                 render.render_created_repos(&mut buf, &created_repos, username);
                 buf.push_str("\n## Repos I've Contributed To\n\n");
                 render.render_contributed_repos(&mut buf, &contributed_repos, username);
+                if !kinds.is_empty() {
+                    buf.push_str("\n## Contributions\n\n");
+                    render.render_contributions(&mut buf, &contributions, &kinds, username);
+                }
+                if let Some(summary) = &summary {
+                    buf.push_str("\n## Summary\n\n");
+                    render.render_summary(&mut buf, summary, username);
+                }
                 std::fs::write(output, buf)?;
             }
             "svg" => {
                 // Scenario 3: Create separate SVG files
-                let render = SvgRenderer::new();
+                let render = SvgRenderer::new()
+                    .with_web_url(client.web_url())
+                    .with_theme(matches.get_one::<Theme>("theme").unwrap().clone())
+                    .with_dark_theme(matches.get_one::<Theme>("dark-theme").cloned())
+                    .with_width(*matches.get_one::<u32>("width").unwrap())
+                    .with_compact(matches.get_flag("compact"))
+                    .with_private_repos(private_repos)
+                    .with_columns(columns.clone());
                 let mut buf = String::new();
                 render.render_created_repos(&mut buf, &created_repos, username);
                 std::fs::write("created.svg", &buf)?;
 
                 buf.clear();
                 render.render_contributed_repos(&mut buf, &contributed_repos, username);
-                std::fs::write("contributed.svg", buf)?;
+                std::fs::write("contributed.svg", &buf)?;
+
+                buf.clear();
+                let weight = *matches
+                    .get_one::<LanguageWeight>("language-weight")
+                    .unwrap();
+                render.render_languages(&mut buf, &created_repos, weight, username);
+                std::fs::write("languages.svg", &buf)?;
+
+                buf.clear();
+                let top_repos = *matches.get_one::<usize>("timeline-repos").unwrap();
+                render.render_timeline(&mut buf, &timeline_repos, top_repos, username);
+                std::fs::write("timeline.svg", &buf)?;
+
+                if !kinds.is_empty() {
+                    buf.clear();
+                    render.render_contributions(&mut buf, &contributions, &kinds, username);
+                    std::fs::write("contributions.svg", &buf)?;
+                }
+
+                if let Some(summary) = &summary {
+                    buf.clear();
+                    render.render_summary(&mut buf, summary, username);
+                    std::fs::write("summary.svg", buf)?;
+                }
             }
             _ => unreachable!("Invalid format"),
         }
@@ -97,43 +484,88 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Adds a hint on how to fix the errors a user can act on.
+fn explain(err: github::Error) -> anyhow::Error {
+    let hint = match &err {
+        github::Error::Unauthorized(_) => "check that --token or GITHUB_TOKEN is a valid token",
+        github::Error::RateLimited { .. } => {
+            "wait for the rate limit to reset, or use a different token"
+        }
+        github::Error::UserNotFound(_) => "check the spelling of --username",
+        github::Error::NotAUser(_) | github::Error::NotAnOrganization(_) => {
+            "pass --owner auto to detect the account kind"
+        }
+        github::Error::Network(_) => "check your network connection and --api-url",
+        _ => return err.into(),
+    };
+    anyhow!("{err}\nhint: {hint}")
+}
+
+/// What `update_markdown` renders between the markers of a file.
+struct Sections<'a> {
+    created_repos: &'a [Repository],
+    contributed_repos: &'a [ContributedRepo],
+    contributions: &'a [RepoContributions],
+    kinds: &'a [ContributionKind],
+    summary: Option<&'a ContributionStats>,
+}
+
 fn update_markdown(
     path: &Path,
-    created_repos: Vec<Repository>,
-    contributed_repos: Vec<ContributedRepo>,
+    render: &MarkdownRenderer,
+    sections: &Sections<'_>,
     username: &str,
 ) -> Result<()> {
-    let render = MarkdownRenderer::new();
-    let mut buf;
-    if !path.exists() {
-        buf = format!(
-            "# My GitHub Contribution Stats
-
-## Repos Created by {username}
-
-<!-- BEGIN:created_repos -->
-<!-- END:created_repos -->
-
-## Repos {username} Contributed To
-
-<!-- BEGIN:contributed -->
-<!-- END:contributed -->
-"
-        );
-    } else {
-        buf = std::fs::read_to_string(path)?;
-    }
+    let mut buf = std::fs::read_to_string(path)?;
     let mut part_buf = String::new();
-    render.render_created_repos(&mut part_buf, &created_repos, username);
+    render.render_created_repos(&mut part_buf, sections.created_repos, username);
     replace_template(&mut buf, "created_repos", &part_buf)?;
     part_buf.clear();
-    render.render_contributed_repos(&mut part_buf, &contributed_repos, username);
+    render.render_contributed_repos(&mut part_buf, sections.contributed_repos, username);
     replace_template(&mut buf, "contributed", &part_buf)?;
+    if !sections.kinds.is_empty() {
+        if has_template(&buf, "contributions") {
+            part_buf.clear();
+            render.render_contributions(
+                &mut part_buf,
+                sections.contributions,
+                sections.kinds,
+                username,
+            );
+            replace_template(&mut buf, "contributions", &part_buf)?;
+        } else {
+            warn_missing_template(path, "contributions");
+        }
+    }
+    // The summary is printed anyway, so it's only written where asked for.
+    if let Some(summary) = sections.summary {
+        if has_template(&buf, "summary") {
+            part_buf.clear();
+            render.render_summary(&mut part_buf, summary, username);
+            replace_template(&mut buf, "summary", &part_buf)?;
+        } else {
+            warn_missing_template(path, "summary");
+        }
+    }
 
     std::fs::write(path, buf)?;
     Ok(())
 }
 
+/// Whether `buf` has both markers of the `name` section.
+fn has_template(buf: &str, name: &str) -> bool {
+    buf.contains(&format!("<!-- BEGIN:{name} -->\n"))
+        && buf.contains(&format!("<!-- END:{name} -->"))
+}
+
+/// Optional sections are only written to files that ask for them.
+fn warn_missing_template(path: &Path, name: &str) {
+    eprintln!(
+        "warning: {} has no <!-- BEGIN:{name} --> and <!-- END:{name} --> markers, skipping the {name} section",
+        path.display()
+    );
+}
+
 fn replace_template(buf: &mut String, name: &str, part_buf: &str) -> Result<()> {
     let start = format!("<!-- BEGIN:{} -->\n", name);
     let end = format!("<!-- END:{} -->", name);
@@ -1,8 +1,14 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use worker::{self, Context, Date, Env, Request, Response, Router, console_log};
+use worker::{self, Context, Date, Env, Request, Response, RouteContext, Router, console_log};
 
-use github_contrib_stats::{github, render::Render, render::SvgRenderer};
+use github_contrib_stats::github::{
+    self, Affiliation, ContributedReposOptions, CreatedReposOptions, GitHubClient, OwnerKind,
+};
+use github_contrib_stats::query::{RepoFilter, RepoOrder, StatsQuery};
+use github_contrib_stats::render::{Column, Render, SvgRenderer};
+use github_contrib_stats::theme::{Theme, ThemeColor};
 
 mod utils;
 
@@ -20,10 +26,143 @@ fn log_request(req: &Request) {
     );
 }
 
-fn to_err(err: anyhow::Error) -> worker::Error {
+fn to_err(err: github::Error) -> worker::Error {
     worker::Error::RustError(err.to_string())
 }
 
+fn error_response(err: &github::Error) -> worker::Result<Response> {
+    Response::error(err.to_string(), err.status_code().as_u16())
+}
+
+fn github_client<D>(ctx: &RouteContext<D>) -> worker::Result<GitHubClient> {
+    let token = ctx.secret("GITHUB_TOKEN")?.to_string();
+    let mut builder = GitHubClient::builder().token(token);
+    if let Ok(api_url) = ctx.var("GITHUB_API_URL") {
+        builder = builder.base_url(api_url.to_string());
+    }
+    builder.build().map_err(to_err)
+}
+
+/// Combines the `include`/`exclude` query params with the `INCLUDE_REPOS`/`EXCLUDE_REPOS` vars.
+fn repo_filter<D>(
+    query: &HashMap<Cow<'_, str>, Cow<'_, str>>,
+    ctx: &RouteContext<D>,
+) -> worker::Result<RepoFilter> {
+    let patterns = |param: &str, var: &str| {
+        let configured = ctx.var(var).map(|value| value.to_string()).ok();
+        let requested = query.get(param).map(ToString::to_string);
+        configured
+            .into_iter()
+            .chain(requested)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    RepoFilter::parse(
+        Some(&patterns("include", "INCLUDE_REPOS")),
+        Some(&patterns("exclude", "EXCLUDE_REPOS")),
+    )
+    .map_err(worker::Error::RustError)
+}
+
+/// Which of the fetched repos a card lists.
+fn stats_query<D>(
+    query: &HashMap<Cow<'_, str>, Cow<'_, str>>,
+    ctx: &RouteContext<D>,
+) -> worker::Result<StatsQuery> {
+    let min = |name: &str| {
+        query
+            .get(name)
+            .map_or(Ok(0), |x| x.parse::<u32>())
+            .map_err(|_| worker::Error::RustError(format!("{name} is not an integer")))
+    };
+    Ok(StatsQuery {
+        filter: repo_filter(query, ctx)?,
+        min_stars: min("min_stars")?,
+        min_forks: min("min_forks")?,
+        min_prs: min("min_prs")?,
+        min_merged: min("min_merged")?,
+        sort: query
+            .get("sort")
+            .map(|x| x.parse::<RepoOrder>())
+            .transpose()
+            .map_err(worker::Error::RustError)?,
+        max_repos: query
+            .get("max_repos")
+            .map(|x| x.parse::<usize>())
+            .transpose()
+            .map_err(|_| worker::Error::RustError("max_repos is not an integer".to_string()))?,
+    })
+}
+
+/// The `theme` preset, with the colours given by the params in [`ThemeColor::ALL`].
+fn theme(query: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> worker::Result<Theme> {
+    let mut theme = query
+        .get("theme")
+        .filter(|x| *x != "auto")
+        .map(|x| x.parse::<Theme>())
+        .transpose()
+        .map_err(worker::Error::RustError)?
+        .unwrap_or_default();
+    for color in ThemeColor::ALL {
+        let param = color.as_str();
+        if let Some(value) = query.get(param) {
+            theme = theme
+                .with_color(param, value)
+                .map_err(worker::Error::RustError)?;
+        }
+    }
+    Ok(theme)
+}
+
+/// The `dark_theme` preset, or the dark one for `theme=auto`.
+fn dark_theme(query: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> worker::Result<Option<Theme>> {
+    match query.get("dark_theme") {
+        Some(x) => x
+            .parse::<Theme>()
+            .map(Some)
+            .map_err(worker::Error::RustError),
+        None => Ok(query
+            .get("theme")
+            .is_some_and(|x| x == "auto")
+            .then(Theme::dark)),
+    }
+}
+
+/// The `width` of the cards, between [`SvgRenderer::MIN_WIDTH`] and [`SvgRenderer::MAX_WIDTH`].
+fn width(query: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> worker::Result<u32> {
+    let range = SvgRenderer::MIN_WIDTH..=SvgRenderer::MAX_WIDTH;
+    query
+        .get("width")
+        .map_or(Ok(SvgRenderer::DEFAULT_WIDTH), |x| {
+            x.parse::<u32>()
+                .ok()
+                .filter(|width| range.contains(width))
+                .ok_or_else(|| {
+                    worker::Error::RustError(format!(
+                        "width must be between {} and {}",
+                        range.start(),
+                        range.end()
+                    ))
+                })
+        })
+}
+
+/// The `columns` of the repository cards, each card's default ones when not given.
+fn columns(query: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> worker::Result<Vec<Column>> {
+    query.get("columns").map_or(Ok(Vec::new()), |x| {
+        x.split(',')
+            .map(|column| column.trim().parse::<Column>())
+            .collect::<Result<_, _>>()
+            .map_err(worker::Error::RustError)
+    })
+}
+
+fn compact(query: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> bool {
+    query
+        .get("compact")
+        .is_some_and(|x| x == "true" || x == "1")
+}
+
 #[worker::event(fetch)]
 pub async fn main(req: Request, env: Env, _ctx: Context) -> worker::Result<Response> {
     log_request(&req);
@@ -34,44 +173,83 @@ pub async fn main(req: Request, env: Env, _ctx: Context) -> worker::Result<Respo
 
     router
         .get("/", |_, _| Response::ok("Hello from Workers!"))
-        .get_async("/created", |req, _ctx| async move {
+        .get_async("/created", |req, ctx| async move {
             let url = req.url()?;
             let query: HashMap<_, _> = url.query_pairs().collect();
             let username = query
                 .get("username")
                 .ok_or_else(|| worker::Error::RustError("name not found".to_string()))?;
-            let max_repos = query
-                .get("max_repos")
-                .map(|x| x.parse::<usize>())
+            let stats = stats_query(&query, &ctx)?;
+            let theme = theme(&query)?;
+            let dark_theme = dark_theme(&query)?;
+            let width = width(&query)?;
+
+            let client = github_client(&ctx)?;
+            let owner = query
+                .get("owner")
+                .map(|x| x.parse::<OwnerKind>())
                 .transpose()
-                .map_err(|_| worker::Error::RustError("max_repos is not an integer".to_string()))?;
+                .map_err(worker::Error::RustError)?
+                .unwrap_or_default();
+            let affiliations = match query.get("affiliation") {
+                Some(value) => value
+                    .split(',')
+                    .map(|x| x.trim().parse::<Affiliation>())
+                    .collect::<Result<_, _>>()
+                    .map_err(worker::Error::RustError)?,
+                None => vec![Affiliation::Owner],
+            };
+            let options = CreatedReposOptions {
+                owner,
+                affiliations,
+                ..CreatedReposOptions::default()
+            };
 
-            let repos = github::get_created_repos(username, max_repos)
-                .await
-                .map_err(to_err)?;
+            let repos = match client.get_created_repos(username, &options).await {
+                Ok(repos) => stats.apply(repos),
+                Err(err) => return error_response(&err),
+            };
 
             let mut buf = String::new();
-            SvgRenderer::new().render_created_repos(&mut buf, &repos, username);
+            SvgRenderer::new()
+                .with_web_url(client.web_url())
+                .with_theme(theme)
+                .with_dark_theme(dark_theme)
+                .with_width(width)
+                .with_compact(compact(&query))
+                .with_columns(columns(&query)?)
+                .render_created_repos(&mut buf, &repos, username);
             Response::ok(buf)
         })
-        .get_async("/contributed", |req, _ctx| async move {
+        .get_async("/contributed", |req, ctx| async move {
             let url = req.url()?;
             let query: HashMap<_, _> = url.query_pairs().collect();
             let username = query
                 .get("username")
                 .ok_or_else(|| worker::Error::RustError("name not found".to_string()))?;
-            let max_repos = query
-                .get("max_repos")
-                .map(|x| x.parse::<usize>())
-                .transpose()
-                .map_err(|_| worker::Error::RustError("max_repos is not an integer".into()))?;
+            let stats = stats_query(&query, &ctx)?;
+            let theme = theme(&query)?;
+            let dark_theme = dark_theme(&query)?;
+            let width = width(&query)?;
 
-            let repos = github::get_contributed_repos(username, max_repos)
+            let client = github_client(&ctx)?;
+            let repos = match client
+                .get_contributed_repos(username, &ContributedReposOptions::default())
                 .await
-                .map_err(to_err)?;
+            {
+                Ok(repos) => stats.apply(repos),
+                Err(err) => return error_response(&err),
+            };
 
             let mut buf = String::new();
-            SvgRenderer::new().render_contributed_repos(&mut buf, &repos, username);
+            SvgRenderer::new()
+                .with_web_url(client.web_url())
+                .with_theme(theme)
+                .with_dark_theme(dark_theme)
+                .with_width(width)
+                .with_compact(compact(&query))
+                .with_columns(columns(&query)?)
+                .render_contributed_repos(&mut buf, &repos, username);
             Response::ok(buf)
         })
         .get_async("/worker-version", |_, ctx| async move {
@@ -0,0 +1,133 @@
+//! Advance widths of the cards' default font, to lay out text without a renderer.
+//!
+//! The widths are Arial's (metric-compatible with Helvetica and Liberation Sans),
+//! in thousandths of the font size.
+
+use std::borrow::Cow;
+
+/// Appended to text cut short by [`truncate`].
+pub const ELLIPSIS: &str = "...";
+
+/// Widths of the printable ASCII characters from `' '` to `'~'`.
+const REGULAR_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0' to '9'
+    278, 278, 584, 584, 584, 556, 1015, // ':' to '@'
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // 'A' to 'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N' to 'Z'
+    278, 278, 278, 469, 556, 333, // '[' to '`'
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // 'a' to 'm'
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // 'n' to 'z'
+    334, 260, 334, 584, // '{' to '~'
+];
+
+const BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // '0' to '9'
+    333, 333, 584, 584, 584, 611, 975, // ':' to '@'
+    722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, // 'A' to 'M'
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // 'N' to 'Z'
+    333, 278, 333, 584, 556, 333, // '[' to '`'
+    556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, // 'a' to 'm'
+    611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, // 'n' to 'z'
+    389, 280, 389, 584, // '{' to '~'
+];
+
+/// East Asian wide characters and emoji take a full em.
+const WIDE_WIDTH: u16 = 1000;
+
+/// Other characters, mostly accented Latin letters, are about as wide as a digit.
+const FALLBACK_WIDTH: u16 = 556;
+
+/// The width of `text` in pixels, set in `font_size` pixels.
+#[must_use]
+pub fn text_width(text: &str, font_size: f64, bold: bool) -> f64 {
+    let units: u32 = text.chars().map(|c| u32::from(char_width(c, bold))).sum();
+    f64::from(units) * font_size / 1000.0
+}
+
+/// Cuts `text` short, ending it with [`ELLIPSIS`], so that it's at most `max_width` pixels wide.
+///
+/// Only ever cuts between characters.
+#[must_use]
+pub fn truncate(text: &str, max_width: f64, font_size: f64, bold: bool) -> Cow<'_, str> {
+    if text_width(text, font_size, bold) <= max_width {
+        return Cow::Borrowed(text);
+    }
+    let max_width = max_width - text_width(ELLIPSIS, font_size, bold);
+    let mut width = 0.0;
+    let mut end = 0;
+    for (i, c) in text.char_indices() {
+        width += f64::from(char_width(c, bold)) * font_size / 1000.0;
+        if width > max_width {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    Cow::Owned(format!("{}{ELLIPSIS}", &text[..end]))
+}
+
+fn char_width(c: char, bold: bool) -> u16 {
+    let widths = if bold { &BOLD_WIDTHS } else { &REGULAR_WIDTHS };
+    match c {
+        ' '..='~' => widths[c as usize - ' ' as usize],
+        _ if is_wide(c) => WIDE_WIDTH,
+        _ => FALLBACK_WIDTH,
+    }
+}
+
+/// Whether `c` is in one of the blocks of CJK, Hangul, full-width forms or emoji.
+fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{303E}'
+            | '\u{3041}'..='\u{33FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F300}'..='\u{1F64F}'
+            | '\u{1F900}'..='\u{1F9FF}'
+            | '\u{20000}'..='\u{2FFFD}'
+            | '\u{30000}'..='\u{3FFFD}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_width_uses_advance_widths() {
+        assert_eq!(text_width("", 14.0, false), 0.0);
+        // 'i' is narrow, 'W' wide, and bold is wider still.
+        assert!(text_width("iiii", 14.0, false) < text_width("WWWW", 14.0, false));
+        assert!(text_width("repo", 14.0, false) < text_width("repo", 14.0, true));
+        assert_eq!(text_width("用户", 10.0, false), 20.0);
+        assert_eq!(text_width("1000", 10.0, false), 22.24);
+    }
+
+    #[test]
+    fn truncate_cuts_between_characters() {
+        assert_eq!(
+            truncate("rust-lang/rust", 200.0, 14.0, false),
+            "rust-lang/rust"
+        );
+
+        let truncated = truncate("rust-lang/rust-analyzer", 100.0, 14.0, false);
+        assert!(truncated.ends_with(ELLIPSIS));
+        assert!(text_width(&truncated, 14.0, false) <= 100.0);
+
+        // Multi-byte names must not be sliced inside a character.
+        let truncated = truncate("用户/项目名称很长的仓库", 80.0, 14.0, false);
+        assert_eq!(truncated, "用户/项目...");
+        assert_eq!(truncate("ñandú/ñandú", 30.0, 14.0, false), "ña...");
+    }
+}
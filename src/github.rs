@@ -1,56 +1,659 @@
 use std::cmp::Reverse;
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::{Context, Result};
 use base64::prelude::*;
-use chrono::{DateTime, Utc};
-use futures::future::join_all;
-use http::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
-use http::{HeaderMap, HeaderValue};
-use log::{error, info};
+use chrono::{DateTime, Days, Months, NaiveDate, NaiveTime, Utc};
+use futures::stream::{self, StreamExt};
+use http::header::{ACCEPT, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use log::{error, info, warn};
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
+use crate::query::StatsQuery;
+
 const PER_PAGE: u8 = 100;
 const MAX_RESULTS: u32 = 1000;
 
-static CLIENT: LazyLock<Client> = LazyLock::new(|| {
-    let token = std::env::var("GITHUB_TOKEN").expect("GITHUB_TOKEN env var not found");
-    let mut headers = HeaderMap::with_capacity(2);
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(USER_AGENT, HeaderValue::from_static("github-contrib-stats"));
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
-    );
-
-    let mut builder = Client::builder().default_headers(headers);
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        use std::time::Duration;
-        builder = builder.connect_timeout(Duration::from_millis(500));
+const DEFAULT_API_URL: &str = "https://api.github.com";
+const DEFAULT_WEB_URL: &str = "https://github.com";
+const DEFAULT_USER_AGENT: &str = "github-contrib-stats";
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_CONCURRENCY: usize = 4;
+/// GitHub's advice for secondary rate limits that come without a `Retry-After`.
+const SECONDARY_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How [`GitHubClient`] retries rate-limited and transiently failing requests.
+///
+/// Waits requested by GitHub through `Retry-After` or `x-ratelimit-reset` are
+/// honoured, otherwise the delay doubles on every attempt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt, `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Longest single wait. A server asking for longer fails the request right away.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
     }
-    #[cfg(target_arch = "wasm32")]
-    {
-        builder = builder;
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+/// Errors returned by the fetch functions in this module.
+#[derive(Debug)]
+pub enum Error {
+    /// The client could not be configured, e.g. no token was given.
+    Config(String),
+    /// No account with this login exists.
+    UserNotFound(String),
+    /// The login belongs to an organization rather than a user.
+    NotAUser(String),
+    /// The login belongs to a user rather than an organization.
+    NotAnOrganization(String),
+    /// The token is missing, invalid or lacks the required scopes.
+    Unauthorized(String),
+    /// Still rate limited after the [`RetryPolicy`] budget was spent.
+    RateLimited {
+        /// When the rate limit window resets, if GitHub told us.
+        reset: Option<DateTime<Utc>>,
+    },
+    /// The request could not be sent or kept failing with a transient status.
+    Network(reqwest::Error),
+    /// The response did not have the expected shape.
+    Decode(String),
+    /// GitHub answered with data, but also with errors for part of it.
+    PartialResults(String),
+    /// Any other error reported by the API.
+    Api(String),
+    /// A fixture could not be recorded, read, or found when replaying.
+    Fixture(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    /// The HTTP status a service should answer with when serving this error.
+    #[must_use]
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Error::UserNotFound(_) | Error::NotAUser(_) | Error::NotAnOrganization(_) => {
+                StatusCode::NOT_FOUND
+            }
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Error::Network(_) | Error::PartialResults(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Error::Config(_) | Error::Decode(_) | Error::Api(_) | Error::Fixture(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Config(message) => write!(f, "invalid GitHub client configuration: {message}"),
+            Error::UserNotFound(login) => write!(f, "user '{login}' not found"),
+            Error::NotAUser(login) => write!(f, "'{login}' is an organization, not a user"),
+            Error::NotAnOrganization(login) => {
+                write!(f, "'{login}' is a user, not an organization")
+            }
+            Error::Unauthorized(message) => write!(f, "GitHub rejected the token: {message}"),
+            Error::RateLimited { reset: Some(reset) } => write!(
+                f,
+                "GitHub API rate limit exceeded, resets at {}",
+                reset.to_rfc3339()
+            ),
+            Error::RateLimited { reset: None } => write!(f, "GitHub API rate limit exceeded"),
+            Error::Network(err) => write!(f, "failed to reach GitHub: {err}"),
+            Error::Decode(message) => write!(f, "failed to decode GitHub response: {message}"),
+            Error::PartialResults(message) => {
+                write!(f, "GitHub returned partial results: {message}")
+            }
+            Error::Api(message) => write!(f, "GitHub API error: {message}"),
+            Error::Fixture(message) => write!(f, "fixture error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+                Error::Unauthorized(err.to_string())
+            }
+            _ if err.is_decode() => Error::Decode(err.to_string()),
+            _ => Error::Network(err),
+        }
+    }
+}
+
+/// Rate limit state reported through the response headers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RateLimit {
+    retry_after: Option<Duration>,
+    remaining: Option<u32>,
+    reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        fn parse<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        Self {
+            retry_after: parse(headers, RETRY_AFTER.as_str()).map(Duration::from_secs),
+            remaining: parse(headers, "x-ratelimit-remaining"),
+            reset: parse(headers, "x-ratelimit-reset")
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.retry_after.is_some() || self.remaining == Some(0)
+    }
+
+    /// Waits out a secondary rate limit for as long as GitHub advises when no header says.
+    fn or_secondary_backoff(self, now: DateTime<Utc>) -> Self {
+        if self.wait(now).is_some() {
+            return self;
+        }
+        Self {
+            retry_after: Some(SECONDARY_RATE_LIMIT_BACKOFF),
+            ..self
+        }
+    }
+
+    /// The wait GitHub asked for, if any.
+    fn wait(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.retry_after.or_else(|| {
+            let reset = self.reset.filter(|_| self.remaining == Some(0))?;
+            Some((reset - now).to_std().unwrap_or_default())
+        })
+    }
+}
+
+/// Secondary rate limits often come as a 403 with only the message to tell them from a bad token.
+fn is_secondary_rate_limit(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("secondary rate limit") || message.contains("abuse")
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Whether the request failed before GitHub could answer it, e.g. by timing out.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_unanswered(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect()
+}
+
+/// Whether the request failed before GitHub could answer it; `fetch` can't tell connecting apart.
+#[cfg(target_arch = "wasm32")]
+fn is_unanswered(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_request()
+}
+
+fn is_graphql_rate_limited(resp: &Value) -> bool {
+    resp.get("errors")
+        .and_then(Value::as_array)
+        .is_some_and(|errors| {
+            errors
+                .iter()
+                .any(|error| error.get("type").and_then(Value::as_str) == Some("RATE_LIMITED"))
+        })
+}
+
+/// A GraphQL request and the response GitHub gave to it, as stored on disk.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+struct Exchange {
+    request: Value,
+    response: Value,
+}
+
+impl Exchange {
+    /// Hand-written fixtures may leave out `query` to match on `variables` only.
+    fn matches(&self, request: &Value) -> bool {
+        if self.request.get("query").is_some() {
+            &self.request == request
+        } else {
+            self.request.get("variables") == request.get("variables")
+        }
+    }
+
+    /// A stable file name, so re-recording the same request overwrites it.
+    fn file_name(&self) -> String {
+        // 64-bit FNV-1a
+        let hash = self
+            .request
+            .to_string()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}.json")
+    }
+
+    fn save(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(self.file_name());
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|err| Error::Fixture(format!("{}: {err}", path.display())))?;
+        std::fs::write(&path, content)
+            .map_err(|err| Error::Fixture(format!("{}: {err}", path.display())))
+    }
+
+    fn load_all(dir: &Path) -> Result<Vec<Self>> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|err| Error::Fixture(format!("{}: {err}", dir.display())))?;
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|err| Error::Fixture(format!("{}: {err}", path.display())))?;
+                serde_json::from_str(&content)
+                    .map_err(|err| Error::Fixture(format!("{}: {err}", path.display())))
+            })
+            .collect()
+    }
+}
+
+/// Where GraphQL requests go.
+#[derive(Clone, Debug)]
+enum Transport {
+    Http,
+    /// Sends requests to GitHub and saves every exchange to the directory.
+    Record(PathBuf),
+    /// Answers requests from previously recorded exchanges, without network access.
+    Replay(Arc<[Exchange]>),
+}
+
+#[derive(Clone, Debug)]
+enum FixtureMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// A GitHub GraphQL API client bound to a single token.
+///
+/// Cloning is cheap, the underlying connection pool is shared.
+#[derive(Clone, Debug)]
+pub struct GitHubClient {
+    http: Client,
+    graphql_url: String,
+    web_url: String,
+    retry_policy: RetryPolicy,
+    max_concurrency: usize,
+    transport: Transport,
+}
+
+/// Builder for [`GitHubClient`], created by [`GitHubClient::builder`].
+#[derive(Clone, Debug)]
+pub struct GitHubClientBuilder {
+    token: Option<String>,
+    base_url: String,
+    web_url: Option<String>,
+    user_agent: String,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    max_concurrency: usize,
+    fixtures: Option<FixtureMode>,
+}
+
+impl Default for GitHubClientBuilder {
+    fn default() -> Self {
+        Self {
+            token: None,
+            base_url: DEFAULT_API_URL.to_string(),
+            web_url: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            timeout: None,
+            retry_policy: RetryPolicy::default(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            fixtures: None,
+        }
+    }
+}
+
+impl GitHubClientBuilder {
+    /// The token used to authenticate every request. Required.
+    #[must_use]
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// The REST API root, the GraphQL endpoint is `{base_url}/graphql`.
+    ///
+    /// For GitHub Enterprise Server this is `https://HOSTNAME/api`.
+    #[must_use]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The web root used for profile and repository links.
+    ///
+    /// Defaults to `https://github.com` for the public API, and to `base_url`
+    /// without its trailing `/api` otherwise.
+    #[must_use]
+    pub fn web_url(mut self, web_url: impl Into<String>) -> Self {
+        self.web_url = Some(web_url.into());
+        self
+    }
+
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Ignored on `wasm32`, where the runtime owns the connection.
+    #[must_use]
+    pub fn connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Ignored on `wasm32`, where the runtime owns the connection.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// How many search pages are fetched at once, at least 1.
+    #[must_use]
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Saves every GraphQL request and its response as a JSON file in `dir`.
+    #[must_use]
+    pub fn record(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixtures = Some(FixtureMode::Record(dir.into()));
+        self
+    }
+
+    /// Answers GraphQL requests from the files written by [`Self::record`]
+    /// instead of GitHub. No token is needed in this mode.
+    #[must_use]
+    pub fn replay(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fixtures = Some(FixtureMode::Replay(dir.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<GitHubClient> {
+        let transport = match self.fixtures {
+            None => Transport::Http,
+            Some(FixtureMode::Record(dir)) => {
+                std::fs::create_dir_all(&dir)
+                    .map_err(|err| Error::Fixture(format!("{}: {err}", dir.display())))?;
+                Transport::Record(dir)
+            }
+            Some(FixtureMode::Replay(dir)) => Transport::Replay(Exchange::load_all(&dir)?.into()),
+        };
+        let token = match self.token {
+            Some(token) => Some(token),
+            None if matches!(transport, Transport::Replay(_)) => None,
+            None => return Err(Error::Config("GitHub token is required".to_string())),
+        };
+
+        let mut headers = HeaderMap::with_capacity(3);
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.v3+json"),
+        );
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_str(&self.user_agent)
+                .map_err(|_| Error::Config("invalid user agent".to_string()))?,
+        );
+        if let Some(token) = token {
+            let mut authorization =
+                HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+                    Error::Config("GitHub token contains invalid characters".to_string())
+                })?;
+            authorization.set_sensitive(true);
+            headers.insert(AUTHORIZATION, authorization);
+        }
+
+        // Timeouts are left to the runtime on wasm, which has nothing else to set.
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut builder = Client::builder().default_headers(headers);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(timeout) = self.connect_timeout {
+                builder = builder.connect_timeout(timeout);
+            }
+            if let Some(timeout) = self.timeout {
+                builder = builder.timeout(timeout);
+            }
+        }
+
+        let base_url = self.base_url.trim_end_matches('/');
+        let web_url = match self.web_url {
+            Some(web_url) => web_url.trim_end_matches('/').to_string(),
+            None if base_url == DEFAULT_API_URL => DEFAULT_WEB_URL.to_string(),
+            None => base_url
+                .strip_suffix("/api")
+                .unwrap_or(base_url)
+                .to_string(),
+        };
+
+        Ok(GitHubClient {
+            http: builder.build()?,
+            graphql_url: format!("{base_url}/graphql"),
+            web_url,
+            retry_policy: self.retry_policy,
+            max_concurrency: self.max_concurrency,
+            transport,
+        })
+    }
+}
+
+impl GitHubClient {
+    #[must_use]
+    pub fn builder() -> GitHubClientBuilder {
+        GitHubClientBuilder::default()
+    }
+
+    /// Builds a client authenticated with the `GITHUB_TOKEN` env var,
+    /// talking to `GITHUB_API_URL` when it is set.
+    pub fn from_env() -> Result<Self> {
+        let token = std::env::var("GITHUB_TOKEN")
+            .map_err(|_| Error::Config("GITHUB_TOKEN env var not found".to_string()))?;
+        let mut builder = Self::builder().token(token);
+        if let Ok(base_url) = std::env::var("GITHUB_API_URL") {
+            builder = builder.base_url(base_url);
+        }
+        builder.build()
+    }
+
+    /// The web root links should point at, without a trailing slash.
+    #[must_use]
+    pub fn web_url(&self) -> &str {
+        &self.web_url
     }
 
-    builder.build().unwrap()
-});
+    async fn post_graphql(&self, body: &Value) -> Result<Value> {
+        match &self.transport {
+            Transport::Http => self.send_graphql(body).await,
+            Transport::Record(dir) => {
+                let response = self.send_graphql(body).await?;
+                let exchange = Exchange {
+                    request: body.clone(),
+                    response,
+                };
+                exchange.save(dir)?;
+                Ok(exchange.response)
+            }
+            Transport::Replay(exchanges) => exchanges
+                .iter()
+                .find(|exchange| exchange.matches(body))
+                .map(|exchange| exchange.response.clone())
+                .ok_or_else(|| {
+                    Error::Fixture(format!(
+                        "no recorded response for variables {}",
+                        body["variables"]
+                    ))
+                }),
+        }
+    }
+
+    /// Posts a GraphQL request, retrying according to the [`RetryPolicy`].
+    async fn send_graphql(&self, body: &Value) -> Result<Value> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+
+            let (error, rate_limit) =
+                match self.http.post(&self.graphql_url).json(body).send().await {
+                    Ok(resp) => {
+                        let status = resp.status();
+                        let rate_limit = RateLimit::from_headers(resp.headers());
+                        match resp.error_for_status_ref() {
+                            Ok(_) => {
+                                let data: Value = resp.json().await?;
+                                if !is_graphql_rate_limited(&data) {
+                                    return Ok(data);
+                                }
+                                (
+                                    Error::RateLimited {
+                                        reset: rate_limit.reset,
+                                    },
+                                    rate_limit,
+                                )
+                            }
+                            Err(_)
+                                if status == StatusCode::TOO_MANY_REQUESTS
+                                    || (status == StatusCode::FORBIDDEN
+                                        && rate_limit.is_exceeded()) =>
+                            {
+                                (
+                                    Error::RateLimited {
+                                        reset: rate_limit.reset,
+                                    },
+                                    rate_limit,
+                                )
+                            }
+                            Err(err) if status == StatusCode::FORBIDDEN => {
+                                let message = resp.text().await.unwrap_or_default();
+                                if !is_secondary_rate_limit(&message) {
+                                    return Err(err.into());
+                                }
+                                (
+                                    Error::RateLimited {
+                                        reset: rate_limit.reset,
+                                    },
+                                    rate_limit.or_secondary_backoff(Utc::now()),
+                                )
+                            }
+                            Err(err) if is_transient(status) => (Error::Network(err), rate_limit),
+                            Err(err) => return Err(err.into()),
+                        }
+                    }
+                    Err(err) if is_unanswered(&err) => (Error::Network(err), RateLimit::default()),
+                    Err(err) => return Err(err.into()),
+                };
+
+            let retry = attempts - 1;
+            let delay = rate_limit
+                .wait(Utc::now())
+                .unwrap_or_else(|| self.retry_policy.backoff(retry));
+            if retry >= self.retry_policy.max_retries || delay > self.retry_policy.max_backoff {
+                return Err(error);
+            }
+
+            warn!("{error}, retrying in {delay:?} (attempt {attempts})");
+            sleep(delay).await;
+        }
+    }
+}
 
-const GRAPHQL_URL: &str = "https://api.github.com/graphql";
 const QUERY_REPOS: &str = "\
-query ($username: String!, $perPage: Int!, $after: String) {
-  user(login: $username) {
+query (
+  $login: String!
+  $affiliations: [RepositoryAffiliation]
+  $isFork: Boolean
+  $privacy: RepositoryPrivacy
+  $perPage: Int!
+  $after: String
+) {
+  repositoryOwner(login: $login) {
+    __typename
     repositories(
-      ownerAffiliations: OWNER
-      isFork: false
+      ownerAffiliations: $affiliations
+      isFork: $isFork
+      privacy: $privacy
       first: $perPage
       after: $after
     ) {
@@ -62,12 +665,15 @@ query ($username: String!, $perPage: Int!, $after: String) {
       edges {
         node {
           nameWithOwner
+          url
           stargazerCount
           forkCount
           primaryLanguage {
             name
           }
+          description
           isArchived
+          isPrivate
           createdAt
           pushedAt
         }
@@ -86,10 +692,14 @@ pub struct PrimaryLanguage {
 #[serde(rename_all = "camelCase")]
 pub struct Repository {
     pub name_with_owner: String,
+    pub url: String,
     pub stargazer_count: u32,
     pub fork_count: u32,
     pub primary_language: Option<PrimaryLanguage>,
+    #[serde(default)]
+    pub description: Option<String>,
     pub is_archived: bool,
+    pub is_private: bool,
     pub created_at: DateTime<Utc>,
     pub pushed_at: Option<DateTime<Utc>>,
 }
@@ -118,9 +728,19 @@ impl Repository {
             .map_or("", |(owner, _)| owner)
     }
 
+    /// The bare name when `login` owns the repository, `owner/name` otherwise.
+    #[must_use]
+    pub fn display_name(&self, login: &str) -> &str {
+        if self.owner().eq_ignore_ascii_case(login) {
+            self.name()
+        } else {
+            &self.name_with_owner
+        }
+    }
+
     #[must_use]
     pub fn html_url(&self) -> String {
-        format!("https://github.com/{}", self.name_with_owner)
+        self.url.clone()
     }
 
     #[must_use]
@@ -131,158 +751,570 @@ impl Repository {
     }
 }
 
-pub async fn get_created_repos(
-    username: &str,
-    max_repos: Option<usize>,
-) -> Result<Vec<Repository>> {
-    info!("Fetching created repos for {username}");
+/// The kind of account whose repositories are listed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum OwnerKind {
+    /// Whatever kind of account the login belongs to.
+    #[default]
+    Auto,
+    User,
+    Organization,
+}
 
-    let mut body = json!({
-        "query": QUERY_REPOS,
-        "variables": {
-            "username": username,
-            "perPage": PER_PAGE,
+impl OwnerKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OwnerKind::Auto => "auto",
+            OwnerKind::User => "user",
+            OwnerKind::Organization => "organization",
         }
-    });
-
-    let mut has_next_page = true;
-    let mut end_cursor = None;
-    let mut repos = Vec::new();
-
-    while has_next_page {
-        info!("Fetching repos after {end_cursor:?}");
-        body["variables"]["after"] = json!(end_cursor);
-
-        let mut resp: Value = CLIENT
-            .clone()
-            .post(GRAPHQL_URL)
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
-
-        check_graphql_errors(&resp)?;
-        check_user_exists(&resp, username)?;
-
-        let repo_result: RepositoryResult =
-            serde_json::from_value(resp["data"]["user"]["repositories"].take())
-                .context("failed to decode GitHub GraphQL user.repositories response")?;
+    }
+}
 
-        has_next_page = repo_result.page_info.has_next_page;
-        end_cursor = repo_result.page_info.end_cursor;
+impl std::fmt::Display for OwnerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-        repos.extend(edge_nodes(repo_result.edges));
+impl std::str::FromStr for OwnerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(OwnerKind::Auto),
+            "user" => Ok(OwnerKind::User),
+            "org" | "organization" => Ok(OwnerKind::Organization),
+            _ => Err(format!(
+                "unknown owner kind '{s}', expected auto, user or organization"
+            )),
+        }
     }
+}
 
-    let mut repos: Vec<_> = repos
-        .into_iter()
-        .filter(|repo| repo.stargazer_count > 0 || repo.fork_count > 0)
-        .collect();
+/// How an account is related to a repository.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Affiliation {
+    /// Repositories the account owns.
+    Owner,
+    /// Repositories the account was added to as a collaborator.
+    Collaborator,
+    /// Repositories of organizations the account is a member of.
+    OrganizationMember,
+}
 
-    repos.sort_by(|a, b| b.stargazer_count.cmp(&a.stargazer_count));
-    if let Some(n) = max_repos {
-        repos.truncate(n);
+impl Affiliation {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Affiliation::Owner => "owner",
+            Affiliation::Collaborator => "collaborator",
+            Affiliation::OrganizationMember => "organization_member",
+        }
     }
 
-    Ok(repos)
+    fn graphql_name(self) -> &'static str {
+        match self {
+            Affiliation::Owner => "OWNER",
+            Affiliation::Collaborator => "COLLABORATOR",
+            Affiliation::OrganizationMember => "ORGANIZATION_MEMBER",
+        }
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
-pub struct ContributedRepo {
-    pub full_name: String,
-    pub stargazer_count: u32,
-    pub pr_count: u32,
-    pub first_pr: PullRequest,
-    pub last_pr: PullRequest,
+impl std::fmt::Display for Affiliation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all = "camelCase")]
-struct PageInfo {
-    has_next_page: bool,
-    end_cursor: Option<String>,
+impl std::str::FromStr for Affiliation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "owner" => Ok(Affiliation::Owner),
+            "collaborator" => Ok(Affiliation::Collaborator),
+            "organization_member" | "org_member" => Ok(Affiliation::OrganizationMember),
+            _ => Err(format!(
+                "unknown affiliation '{s}', expected owner, collaborator or organization_member"
+            )),
+        }
+    }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-struct Edge<T> {
-    node: T,
+/// A time window, open-ended where a bound is `None`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DateRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
 }
 
-fn edge_nodes<T>(edges: Vec<Option<Edge<T>>>) -> impl Iterator<Item = T> {
-    edges.into_iter().flatten().map(|Edge { node }| node)
-}
+impl DateRange {
+    /// Parses the bounds the way `--since` and `--until` accept them, see [`parse_date_bound`].
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self, String> {
+        let now = Utc::now();
+        let range = Self {
+            since: since
+                .map(|since| parse_date_bound(since, now, false))
+                .transpose()?,
+            until: until
+                .map(|until| parse_date_bound(until, now, true))
+                .transpose()?,
+        };
+        if let (Some(since), Some(until)) = (range.since, range.until)
+            && since > until
+        {
+            return Err(format!(
+                "since ({}) is after until ({})",
+                since.to_rfc3339(),
+                until.to_rfc3339()
+            ));
+        }
+        Ok(range)
+    }
 
-fn check_graphql_errors(resp: &Value) -> Result<()> {
-    let Some(errors) = resp.get("errors") else {
-        return Ok(());
-    };
+    #[must_use]
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
 
-    let Some(error) = errors.as_array().and_then(|arr| arr.first()) else {
-        return Err(anyhow::anyhow!("GitHub API returned errors: {errors}"));
-    };
+    #[must_use]
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| since <= time) && self.until.is_none_or(|until| time <= until)
+    }
 
-    let error_type = error
-        .get("type")
-        .and_then(|t| t.as_str())
-        .unwrap_or("UNKNOWN");
-    let message = error
-        .get("message")
-        .and_then(|m| m.as_str())
-        .unwrap_or("Unknown error");
+    /// Whether a repository created at `created_at` and last pushed at
+    /// `pushed_at` existed and was pushed to within the range.
+    fn overlaps(&self, created_at: DateTime<Utc>, pushed_at: Option<DateTime<Utc>>) -> bool {
+        self.until.is_none_or(|until| created_at <= until)
+            && self
+                .since
+                .is_none_or(|since| pushed_at.unwrap_or(created_at) >= since)
+    }
 
-    if error_type == "NOT_FOUND" {
-        return Err(anyhow::anyhow!("User not found: {message}"));
+    /// The `created:` search qualifier for this range, if it is bounded.
+    fn search_qualifier(&self) -> Option<String> {
+        (!self.is_unbounded()).then(|| format!("created:{self}"))
     }
 
-    Err(anyhow::anyhow!("GitHub API error: {message}"))
+    /// The `created:` search qualifier for the part of this range before `end`.
+    fn search_qualifier_before(&self, end: DateTime<Utc>) -> String {
+        match self.since {
+            // Search timestamps have a one second resolution.
+            Some(since) => format!(
+                "created:{}..{}",
+                since.to_rfc3339(),
+                (end - chrono::Duration::seconds(1)).to_rfc3339()
+            ),
+            None => format!("created:<{}", end.to_rfc3339()),
+        }
+    }
 }
 
-fn check_user_exists(resp: &Value, username: &str) -> Result<()> {
-    if resp["data"]["user"].is_null() {
-        return Err(anyhow::anyhow!(
-            "User '{username}' not found or is not a user account"
-        ));
+/// Formats as the `since..until` search range, with `*` for a missing bound.
+impl std::fmt::Display for DateRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bound =
+            |bound: Option<DateTime<Utc>>| bound.map_or("*".to_string(), |t| t.to_rfc3339());
+        write!(f, "{}..{}", bound(self.since), bound(self.until))
     }
-
-    Ok(())
 }
 
-fn repository_name_from_pull_request_url(url: &str) -> Option<String> {
-    let mut segments = url.rsplit('/');
-    let _pull_number = segments.next()?;
-    let pulls = segments.next()?;
-    let repo = segments.next()?;
-    let owner = segments.next()?;
-
-    if pulls != "pull" {
-        return None;
+/// Parses an RFC 3339 timestamp, a `YYYY-MM-DD` date, a `YYYY` year, or a
+/// time before `now` such as `30d`, `2w`, `6m` or `1y`.
+///
+/// Dates and years start at midnight UTC, or end just before the next one
+/// with `end_of_period`. Relative times are rounded down to midnight so that
+/// repeated queries on the same day match.
+pub fn parse_date_bound(
+    value: &str,
+    now: DateTime<Utc>,
+    end_of_period: bool,
+) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc();
+    let end_of_day =
+        |date: NaiveDate| midnight(date) + chrono::Duration::days(1) - chrono::Duration::seconds(1);
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(if end_of_period {
+            end_of_day(date)
+        } else {
+            midnight(date)
+        });
+    }
+    if value.len() == 4
+        && let Ok(year) = value.parse::<i32>()
+        && let (Some(first), Some(last)) = (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year, 12, 31),
+        )
+    {
+        return Ok(if end_of_period {
+            end_of_day(last)
+        } else {
+            midnight(first)
+        });
     }
 
-    Some(format!("{owner}/{repo}"))
+    let invalid = || {
+        format!(
+            "invalid date '{value}', expected YYYY-MM-DD, YYYY, or a relative time like 30d, 2w, 6m or 1y"
+        )
+    };
+    let (split, _) = value.char_indices().next_back().ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u32 = amount.parse().map_err(|_| invalid())?;
+    let today = now.date_naive();
+    let date = match unit {
+        "d" => today.checked_sub_days(Days::new(amount.into())),
+        "w" => today.checked_sub_days(Days::new(u64::from(amount) * 7)),
+        "m" => today.checked_sub_months(Months::new(amount)),
+        "y" => today.checked_sub_months(Months::new(amount.saturating_mul(12))),
+        _ => return Err(invalid()),
+    };
+    date.map(midnight).ok_or_else(invalid)
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct RepositoryWithStargazerCount {
-    pub stargazer_count: u32,
+/// Which repositories [`GitHubClient::get_created_repos`] lists.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatedReposOptions {
+    pub owner: OwnerKind,
+    /// Repositories related to the account in any of these ways are listed.
+    pub affiliations: Vec<Affiliation>,
+    pub include_forks: bool,
+    pub include_archived: bool,
+    /// Keep repositories nobody has starred or forked.
+    pub include_unstarred: bool,
+    /// Keep private repositories, which are only visible when the token
+    /// has the `repo` scope and access to them.
+    pub include_private: bool,
+    /// Keep repositories created before the end of the range and pushed to after its start.
+    pub range: DateRange,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct PullRequest {
-    pub url: String,
-    pub created_at: DateTime<Utc>,
-    pub repository: RepositoryWithStargazerCount,
+impl Default for CreatedReposOptions {
+    fn default() -> Self {
+        Self {
+            owner: OwnerKind::default(),
+            affiliations: vec![Affiliation::Owner],
+            include_forks: false,
+            include_archived: true,
+            include_unstarred: false,
+            include_private: false,
+            range: DateRange::default(),
+        }
+    }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+/// Which contributions [`GitHubClient::get_contributed_repos`] and
+/// [`GitHubClient::get_contributions`] count.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributedReposOptions {
+    /// Only count contributions created within the range.
+    pub range: DateRange,
+    /// Also count contributions to private repositories, which are only
+    /// visible when the token has the `repo` scope and access to them.
+    pub include_private: bool,
+    /// Keep contributed repositories that have been archived since.
+    pub include_archived: bool,
+}
+
+impl Default for ContributedReposOptions {
+    fn default() -> Self {
+        Self {
+            range: DateRange::default(),
+            include_private: false,
+            include_archived: true,
+        }
+    }
+}
+
+/// Fetches `username`'s created repositories with a client from [`GitHubClient::from_env`].
+pub async fn get_created_repos(
+    username: &str,
+    max_repos: Option<usize>,
+) -> Result<Vec<Repository>> {
+    let repos = GitHubClient::from_env()?
+        .get_created_repos(username, &CreatedReposOptions::default())
+        .await?;
+    Ok(StatsQuery::with_max_repos(max_repos).apply(repos))
+}
+
+/// Fetches `username`'s contributed repositories with a client from [`GitHubClient::from_env`].
+pub async fn get_contributed_repos(
+    username: &str,
+    max_repos: Option<usize>,
+) -> Result<Vec<ContributedRepo>> {
+    let repos = GitHubClient::from_env()?
+        .get_contributed_repos(username, &ContributedReposOptions::default())
+        .await?;
+    Ok(StatsQuery::with_max_repos(max_repos).apply(repos))
+}
+
+impl GitHubClient {
+    /// Lists all repositories owned by `login`, a user or an organization,
+    /// most stars first. Narrow them down with a [`StatsQuery`].
+    pub async fn get_created_repos(
+        &self,
+        login: &str,
+        options: &CreatedReposOptions,
+    ) -> Result<Vec<Repository>> {
+        info!("Fetching created repos for {login}");
+
+        let mut body = json!({
+            "query": QUERY_REPOS,
+            "variables": {
+                "login": login,
+                "affiliations": options
+                    .affiliations
+                    .iter()
+                    .map(|affiliation| affiliation.graphql_name())
+                    .collect::<Vec<_>>(),
+                // null lists both forks and sources, or both visibilities
+                "isFork": (!options.include_forks).then_some(false),
+                "privacy": (!options.include_private).then_some("PUBLIC"),
+                "perPage": PER_PAGE,
+            }
+        });
+
+        let mut has_next_page = true;
+        let mut end_cursor = None;
+        let mut repos = Vec::new();
+
+        while has_next_page {
+            info!("Fetching repos after {end_cursor:?}");
+            body["variables"]["after"] = json!(end_cursor);
+
+            let mut resp = self.post_graphql(&body).await?;
+
+            check_owner(&resp, login, options.owner)?;
+            check_graphql_errors(&resp)?;
+
+            let repo_result: RepositoryResult =
+                serde_json::from_value(resp["data"]["repositoryOwner"]["repositories"].take())
+                    .map_err(|err| Error::Decode(format!("repositoryOwner.repositories: {err}")))?;
+
+            has_next_page = repo_result.page_info.has_next_page;
+            end_cursor = repo_result.page_info.end_cursor;
+
+            repos.extend(edge_nodes(repo_result.edges));
+        }
+
+        let mut repos: Vec<_> = repos
+            .into_iter()
+            .filter(|repo| options.include_archived || !repo.is_archived)
+            .filter(|repo| options.range.overlaps(repo.created_at, repo.pushed_at))
+            .filter(|repo| {
+                options.include_unstarred || repo.stargazer_count > 0 || repo.fork_count > 0
+            })
+            .collect();
+
+        repos.sort_by_key(|repo| Reverse(repo.stargazer_count));
+
+        Ok(repos)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct ContributedRepo {
+    pub full_name: String,
+    pub html_url: String,
+    pub stargazer_count: u32,
+    pub fork_count: u32,
+    pub primary_language: Option<PrimaryLanguage>,
+    pub description: Option<String>,
+    pub is_archived: bool,
+    pub is_private: bool,
+    pub owner_avatar_url: String,
+    pub pr_count: u32,
+    pub merged_count: u32,
+    pub open_count: u32,
+    /// PRs closed without being merged.
+    pub closed_count: u32,
+    pub additions: u32,
+    pub deletions: u32,
+    pub changed_files: u32,
+    pub commit_count: u32,
+    pub first_pr: PullRequest,
+    pub last_pr: PullRequest,
+    /// When each PR was opened, oldest first.
+    pub pr_dates: Vec<DateTime<Utc>>,
+}
+
+impl ContributedRepo {
+    #[must_use]
+    pub fn language(&self) -> &str {
+        self.primary_language
+            .as_ref()
+            .map_or("N/A", |l| l.name.as_str())
+    }
+
+    /// Lines added plus lines deleted across all PRs.
+    #[must_use]
+    pub fn lines_changed(&self) -> u32 {
+        self.additions + self.deletions
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-struct PullRequestSearchResult {
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct Edge<T> {
+    node: T,
+}
+
+fn edge_nodes<T>(edges: Vec<Option<Edge<T>>>) -> impl Iterator<Item = T> {
+    edges.into_iter().flatten().map(|Edge { node }| node)
+}
+
+fn check_graphql_errors(resp: &Value) -> Result<()> {
+    let Some(errors) = resp.get("errors") else {
+        return Ok(());
+    };
+
+    let Some(error) = errors.as_array().and_then(|arr| arr.first()) else {
+        return Err(Error::Api(format!("GitHub API returned errors: {errors}")));
+    };
+
+    let error_type = error
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("UNKNOWN");
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or("Unknown error")
+        .to_string();
+
+    match error_type {
+        "RATE_LIMITED" => Err(Error::RateLimited { reset: None }),
+        "FORBIDDEN" | "INSUFFICIENT_SCOPES" => Err(Error::Unauthorized(message)),
+        _ if resp
+            .get("data")
+            .and_then(Value::as_object)
+            .is_some_and(|data| data.values().any(|value| !value.is_null())) =>
+        {
+            Err(Error::PartialResults(message))
+        }
+        _ => Err(Error::Api(message)),
+    }
+}
+
+fn check_owner(resp: &Value, login: &str, kind: OwnerKind) -> Result<()> {
+    let Some(data) = resp.get("data").filter(|data| data.is_object()) else {
+        return Ok(());
+    };
+
+    match (data["repositoryOwner"]["__typename"].as_str(), kind) {
+        (None, _) => Err(Error::UserNotFound(login.to_string())),
+        (Some("Organization"), OwnerKind::User) => Err(Error::NotAUser(login.to_string())),
+        (Some("User"), OwnerKind::Organization) => Err(Error::NotAnOrganization(login.to_string())),
+        _ => Ok(()),
+    }
+}
+
+fn repository_url_from_pull_request_url(url: &str) -> Option<&str> {
+    url.rsplit_once("/pull/").map(|(repo_url, _)| repo_url)
+}
+
+fn repository_name_from_pull_request_url(url: &str) -> Option<String> {
+    let mut segments = url.rsplit('/');
+    let _pull_number = segments.next()?;
+    let pulls = segments.next()?;
+    let repo = segments.next()?;
+    let owner = segments.next()?;
+
+    if pulls != "pull" {
+        return None;
+    }
+
+    Some(format!("{owner}/{repo}"))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestRepository {
+    pub name_with_owner: String,
+    pub stargazer_count: u32,
+    pub fork_count: u32,
+    pub primary_language: Option<PrimaryLanguage>,
+    pub description: Option<String>,
+    pub is_archived: bool,
+    pub is_private: bool,
+    pub owner: RepositoryOwner,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryOwner {
+    pub avatar_url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    pub state: PullRequestState,
+    pub additions: u32,
+    pub deletions: u32,
+    pub changed_files: u32,
+    pub commits: CommitCount,
+    pub repository: PullRequestRepository,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitCount {
+    pub total_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PullRequestState {
+    Open,
+    /// Closed without being merged.
+    Closed,
+    Merged,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult<T> {
     issue_count: u32,
+    #[serde(default)]
+    discussion_count: u32,
     page_info: PageInfo,
-    edges: Vec<Option<Edge<PullRequest>>>,
+    edges: Vec<Option<Edge<T>>>,
+}
+
+impl<T> SearchResult<T> {
+    /// Matches of the search, counted in `discussionCount` for discussions.
+    fn total_count(&self) -> u32 {
+        self.issue_count.max(self.discussion_count)
+    }
+}
+
+/// A search result the `created:<` windows can be ordered by.
+trait SearchNode: DeserializeOwned {
+    fn created_at(&self) -> DateTime<Utc>;
+}
+
+impl SearchNode for PullRequest {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
 }
 
 const QUERY_PRS: &str = "\
@@ -297,8 +1329,26 @@ query ($q: String!, $perPage: Int!, $cursor: String) {
         ... on PullRequest {
           url
           createdAt
+          state
+          additions
+          deletions
+          changedFiles
+          commits {
+            totalCount
+          }
           repository {
+            nameWithOwner
             stargazerCount
+            forkCount
+            primaryLanguage {
+              name
+            }
+            description
+            isArchived
+            isPrivate
+            owner {
+              avatarUrl
+            }
           }
         }
       }
@@ -307,138 +1357,176 @@ query ($q: String!, $perPage: Int!, $cursor: String) {
   }
 }";
 
-async fn get_one_page_of_pr(
-    mut body: Value,
-    cursor: Option<String>,
-) -> Result<PullRequestSearchResult> {
-    body["variables"]["cursor"] = json!(cursor);
-
-    let mut data: Value = CLIENT
-        .clone()
-        .post(GRAPHQL_URL)
-        .json(&body)
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
-        .await?;
-
-    check_graphql_errors(&data)?;
+impl GitHubClient {
+    async fn get_one_page<T: SearchNode>(
+        &self,
+        mut body: Value,
+        cursor: Option<String>,
+    ) -> Result<SearchResult<T>> {
+        body["variables"]["cursor"] = json!(cursor);
 
-    let result: PullRequestSearchResult = serde_json::from_value(data["data"]["search"].take())
-        .context("failed to decode GitHub GraphQL search response")?;
-    Ok(result)
-}
+        let mut data = self.post_graphql(&body).await?;
 
-/// For one query, GitHub only returns up to 1000 results (`MAX_RESULTS`) at most, with a maximum of 100 results per page.
-/// This function fetches up to `MAX_RESULTS` PRs, paginating if necessary.
-/// For results beyond `MAX_RESULTS`, it will be fetched in subsequent queries with limited with the `created:<YYYY-MM-DD` filter.
-async fn get_all_pages_of_pr(body: Value, total: Option<u32>) -> Result<(u32, Vec<PullRequest>)> {
-    let mut all_prs = Vec::new();
+        check_graphql_errors(&data)?;
 
-    // total not known, fetch first page to get it
-    let (total_all_query, beginning) = if let Some(total_all_query) = total {
-        (total_all_query, 0)
-    } else {
-        let result = get_one_page_of_pr(body.clone(), None).await?;
-        all_prs.extend(edge_nodes(result.edges));
-        (result.issue_count, u32::from(PER_PAGE))
-    };
-    let total_this_query = MAX_RESULTS.min(total_all_query);
-
-    // has more pages
-    if total_this_query > beginning {
-        all_prs.reserve((total_this_query - beginning) as usize);
-
-        // cursor begins with base64("cursor:1")
-        let results = join_all(
-            (beginning..total_this_query)
-                .step_by(usize::from(PER_PAGE))
-                .map(|cursor_offset| {
-                    let page_body = body.clone();
-
-                    async move {
-                        info!("fetching PRs after cursor: {cursor_offset}");
-                        let cursor = BASE64_STANDARD.encode(format!("cursor:{cursor_offset}"));
-                        get_one_page_of_pr(page_body, Some(cursor))
-                            .await
-                            .with_context(|| {
-                                format!("failed to fetch PR page after cursor {cursor_offset}")
-                            })
-                    }
-                }),
-        )
-        .await;
-        for result in results {
-            all_prs.extend(edge_nodes(result?.edges));
-        }
+        let result: SearchResult<T> = serde_json::from_value(data["data"]["search"].take())
+            .map_err(|err| Error::Decode(format!("search: {err}")))?;
+        Ok(result)
     }
 
-    Ok((total_all_query, all_prs))
-}
-
-pub async fn get_contributed_repos(
-    username: &str,
-    max_repos: Option<usize>,
-) -> Result<Vec<ContributedRepo>> {
-    // https://docs.github.com/en/rest/search?apiVersion=2022-11-28
-    // For authenticated requests, you can make up to 30 requests per minute for all search endpoints except for the "Search code" endpoint.
-    // The "Search code" endpoint requires you to authenticate and limits you to 10 requests per minute.
-    // For unauthenticated requests, the rate limit allows you to make up to 10 requests per minute.
-
-    // search returns 1000 results max, regardless of the actual matches, use `created:<YYYY-MM-DD` to filter
-    // sort:created or sort:created-desc (default)
-    // https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests
-    // -user:USERNAME to exclude PRs from repos owned by USERNAME itself
-
-    info!("Fetching contributed repos for {username}");
+    /// For one query, GitHub only returns up to 1000 results (`MAX_RESULTS`) at most, with a maximum of 100 results per page.
+    /// This function fetches up to `MAX_RESULTS` results, paginating if necessary.
+    /// For results beyond `MAX_RESULTS`, it will be fetched in subsequent queries with limited with the `created:<YYYY-MM-DD` filter.
+    async fn get_all_pages<T: SearchNode>(
+        &self,
+        body: Value,
+        total: Option<u32>,
+    ) -> Result<(u32, Vec<T>)> {
+        let mut all_results = Vec::new();
+
+        // total not known, fetch first page to get it
+        let (total_all_query, beginning) = if let Some(total_all_query) = total {
+            (total_all_query, 0)
+        } else {
+            let result = self.get_one_page(body.clone(), None).await?;
+            let total_all_query = result.total_count();
+            all_results.extend(edge_nodes(result.edges));
+            (total_all_query, u32::from(PER_PAGE))
+        };
+        let total_this_query = MAX_RESULTS.min(total_all_query);
+
+        // has more pages
+        if total_this_query > beginning {
+            all_results.reserve((total_this_query - beginning) as usize);
+
+            // cursor begins with base64("cursor:1")
+            let results: Vec<_> = stream::iter(
+                (beginning..total_this_query)
+                    .step_by(usize::from(PER_PAGE))
+                    .map(|cursor_offset| {
+                        let page_body = body.clone();
+
+                        async move {
+                            info!("fetching search results after cursor: {cursor_offset}");
+                            let cursor = BASE64_STANDARD.encode(format!("cursor:{cursor_offset}"));
+                            self.get_one_page::<T>(page_body, Some(cursor))
+                                .await
+                                .inspect_err(|err| {
+                                    error!("failed to fetch search page after cursor {cursor_offset}: {err}");
+                                })
+                        }
+                    }),
+            )
+            .buffered(self.max_concurrency)
+            .collect()
+            .await;
+            for result in results {
+                all_results.extend(edge_nodes(result?.edges));
+            }
+        }
 
-    let first_query =
-        format!("author:{username} type:pr is:public sort:created-desc -user:{username}");
+        Ok((total_all_query, all_results))
+    }
 
-    let mut body = json!({
-        "query": QUERY_PRS,
-        "variables": {
-            "q": first_query,
-            "perPage": PER_PAGE,
+    /// Fetches every result of the search in `body` created within `range`, past
+    /// `MAX_RESULTS`, by repeating it for results created before the oldest one seen.
+    async fn search_all<T: SearchNode>(
+        &self,
+        mut body: Value,
+        range: &DateRange,
+    ) -> Result<Vec<T>> {
+        let base_query = body["variables"]["q"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        if let Some(qualifier) = range.search_qualifier() {
+            body["variables"]["q"] = json!(format!("{base_query} {qualifier}"));
         }
-    });
+        let (total_count, results) = self.get_all_pages::<T>(body.clone(), None).await?;
 
-    let (total_count, prs) = get_all_pages_of_pr(body.clone(), None).await?;
+        let mut min_created_at = match results.last() {
+            Some(result) => result.created_at(),
+            None => return Ok(Vec::new()),
+        };
 
-    let mut min_created_at = match prs.last() {
-        Some(pr) => pr.created_at,
-        None => return Ok(Vec::new()),
-    };
+        let mut all_results = Vec::with_capacity(total_count as usize);
+        all_results.extend(results);
+
+        let mut remaining_count = total_count.saturating_sub(MAX_RESULTS);
+        while remaining_count > 0 {
+            info!(
+                "total: {}, remaining: {}, min_created_at: {}",
+                total_count,
+                remaining_count,
+                min_created_at.to_rfc3339()
+            );
+
+            body["variables"]["q"] = json!(format!(
+                "{} {}",
+                base_query,
+                range.search_qualifier_before(min_created_at)
+            ));
+            let (_, results) = self
+                .get_all_pages::<T>(body.clone(), Some(remaining_count))
+                .await?;
+            match results.last() {
+                Some(result) => min_created_at = result.created_at(),
+                None => break,
+            }
+            all_results.extend(results);
+            remaining_count = remaining_count.saturating_sub(MAX_RESULTS);
+        }
 
-    let mut all_prs = Vec::with_capacity(total_count as usize);
-    all_prs.extend(prs);
+        Ok(all_results)
+    }
 
-    let mut remaining_count = total_count.saturating_sub(MAX_RESULTS);
-    while remaining_count > 0 {
-        info!(
-            "total: {}, remaining: {}, min_created_at: {}",
-            total_count,
-            remaining_count,
-            min_created_at.to_rfc3339()
-        );
+    /// Lists the repositories `username` opened PRs in, most PRs first.
+    pub async fn get_contributed_repos(
+        &self,
+        username: &str,
+        options: &ContributedReposOptions,
+    ) -> Result<Vec<ContributedRepo>> {
+        let prs = self.get_pull_requests(username, options).await?;
+        Ok(group_pull_requests(&prs, options))
+    }
 
-        body["variables"]["q"] = json!(format!(
-            "{} created:<{}",
-            first_query,
-            min_created_at.to_rfc3339()
-        ));
-        let (_, prs) = get_all_pages_of_pr(body.clone(), Some(remaining_count)).await?;
-        match prs.last() {
-            Some(pr) => min_created_at = pr.created_at,
-            None => break,
-        }
-        all_prs.extend(prs);
-        remaining_count = remaining_count.saturating_sub(MAX_RESULTS);
+    /// Lists the PRs `username` opened in repositories of others, newest first.
+    pub async fn get_pull_requests(
+        &self,
+        username: &str,
+        options: &ContributedReposOptions,
+    ) -> Result<Vec<PullRequest>> {
+        // https://docs.github.com/en/rest/search?apiVersion=2022-11-28
+        // For authenticated requests, you can make up to 30 requests per minute for all search endpoints except for the "Search code" endpoint.
+        // The "Search code" endpoint requires you to authenticate and limits you to 10 requests per minute.
+        // For unauthenticated requests, the rate limit allows you to make up to 10 requests per minute.
+
+        // search returns 1000 results max, regardless of the actual matches, use `created:<YYYY-MM-DD` to filter
+        // sort:created or sort:created-desc (default)
+        // https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests
+        // -user:USERNAME to exclude PRs from repos owned by USERNAME itself
+
+        info!("Fetching pull requests for {username}");
+
+        let body = json!({
+            "query": QUERY_PRS,
+            "variables": {
+                "q": ContributionKind::PullRequest.search_query(username, options.include_private),
+                "perPage": PER_PAGE,
+            }
+        });
+        self.search_all(body, &options.range).await
     }
+}
 
+/// Groups `prs` into the repositories they were opened in, most PRs first.
+#[must_use]
+pub fn group_pull_requests(
+    prs: &[PullRequest],
+    options: &ContributedReposOptions,
+) -> Vec<ContributedRepo> {
     let mut groups: HashMap<String, Vec<_>> = HashMap::new();
-    for pr in all_prs {
+    for pr in prs {
         let Some(repo_name) = repository_name_from_pull_request_url(&pr.url) else {
             error!("failed to parse repository name from PR URL: {}", pr.url);
             continue;
@@ -450,27 +1538,271 @@ pub async fn get_contributed_repos(
     let mut repos: Vec<_> = groups
         .into_iter()
         .filter_map(|(repo_name, mut prs)| {
-            prs.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-            let first_pr = prs.first()?.clone();
-            let last_pr = prs.last()?.clone();
+            prs.sort_by_key(|pr| pr.created_at);
+            let first_pr = (*prs.first()?).clone();
+            let last_pr = (*prs.last()?).clone();
             let pr_count = u32::try_from(prs.len()).ok()?;
-
+            let html_url = repository_url_from_pull_request_url(&last_pr.url)?.to_string();
+            let sum = |f: fn(&PullRequest) -> u32| prs.iter().map(|pr| f(pr)).sum();
+            let count_state = |state| {
+                prs.iter()
+                    .filter(|pr| pr.state == state)
+                    .count()
+                    .try_into()
+                    .ok()
+            };
+
+            // The latest PR has the freshest view of the repository.
+            let repository = &last_pr.repository;
             Some(ContributedRepo {
                 full_name: repo_name,
-                stargazer_count: last_pr.repository.stargazer_count,
+                html_url,
+                stargazer_count: repository.stargazer_count,
+                fork_count: repository.fork_count,
+                primary_language: repository.primary_language.clone(),
+                description: repository.description.clone(),
+                is_archived: repository.is_archived,
+                is_private: repository.is_private,
+                owner_avatar_url: repository.owner.avatar_url.clone(),
                 pr_count,
+                merged_count: count_state(PullRequestState::Merged)?,
+                open_count: count_state(PullRequestState::Open)?,
+                closed_count: count_state(PullRequestState::Closed)?,
+                additions: sum(|pr| pr.additions),
+                deletions: sum(|pr| pr.deletions),
+                changed_files: sum(|pr| pr.changed_files),
+                commit_count: sum(|pr| pr.commits.total_count),
+                pr_dates: prs.iter().map(|pr| pr.created_at).collect(),
                 first_pr,
                 last_pr,
             })
         })
+        .filter(|repo| options.include_archived || !repo.is_archived)
         .collect();
 
     repos.sort_by_key(|repo| Reverse((repo.pr_count, repo.last_pr.created_at)));
-    if let Some(n) = max_repos {
-        repos.truncate(n);
+    repos
+}
+
+/// A way of contributing to a repository owned by someone else.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ContributionKind {
+    /// Pull requests the user opened.
+    PullRequest,
+    /// Issues the user opened.
+    Issue,
+    /// Pull requests by others that the user reviewed.
+    Review,
+    /// Discussions where a comment of the user was marked as the answer.
+    DiscussionAnswer,
+}
+
+impl ContributionKind {
+    pub const ALL: [ContributionKind; 4] = [
+        ContributionKind::PullRequest,
+        ContributionKind::Issue,
+        ContributionKind::Review,
+        ContributionKind::DiscussionAnswer,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContributionKind::PullRequest => "pr",
+            ContributionKind::Issue => "issue",
+            ContributionKind::Review => "review",
+            ContributionKind::DiscussionAnswer => "discussion_answer",
+        }
+    }
+
+    fn search_type(self) -> &'static str {
+        match self {
+            ContributionKind::DiscussionAnswer => "DISCUSSION",
+            _ => "ISSUE",
+        }
+    }
+
+    /// The search query matching this kind of contribution by `username`.
+    fn search_query(self, username: &str, include_private: bool) -> String {
+        let qualifiers = match self {
+            ContributionKind::PullRequest => format!("author:{username} type:pr"),
+            ContributionKind::Issue => format!("author:{username} type:issue"),
+            ContributionKind::Review => {
+                format!("reviewed-by:{username} -author:{username} type:pr")
+            }
+            ContributionKind::DiscussionAnswer => format!("answered-by:{username}"),
+        };
+        let visibility = if include_private { "" } else { " is:public" };
+        format!("{qualifiers}{visibility} sort:created-desc -user:{username}")
+    }
+}
+
+impl std::fmt::Display for ContributionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ContributionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pr" | "pull_request" => Ok(ContributionKind::PullRequest),
+            "issue" => Ok(ContributionKind::Issue),
+            "review" => Ok(ContributionKind::Review),
+            "discussion_answer" | "answer" => Ok(ContributionKind::DiscussionAnswer),
+            _ => Err(format!(
+                "unknown contribution kind '{s}', expected pr, issue, review or discussion_answer"
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryRef {
+    pub name_with_owner: String,
+    pub url: String,
+    pub stargazer_count: u32,
+    pub is_private: bool,
+}
+
+/// An issue, pull request or discussion found by a contribution search.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Contribution {
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    pub repository: RepositoryRef,
+}
+
+impl SearchNode for Contribution {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// How often a user contributed to one repository, per [`ContributionKind`].
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct RepoContributions {
+    pub full_name: String,
+    pub html_url: String,
+    pub stargazer_count: u32,
+    pub is_private: bool,
+    /// Only kinds with at least one contribution have an entry.
+    pub counts: BTreeMap<ContributionKind, u32>,
+    pub first_contribution_at: DateTime<Utc>,
+    pub last_contribution_at: DateTime<Utc>,
+}
+
+impl RepoContributions {
+    #[must_use]
+    pub fn count(&self, kind: ContributionKind) -> u32 {
+        self.counts.get(&kind).copied().unwrap_or_default()
     }
 
-    Ok(repos)
+    /// Contributions of all kinds.
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+}
+
+const QUERY_CONTRIBUTIONS: &str = "\
+query ($q: String!, $type: SearchType!, $perPage: Int!, $cursor: String) {
+  search(type: $type, query: $q, first: $perPage, after: $cursor) {
+    pageInfo {
+      hasNextPage
+      endCursor
+    }
+    edges {
+      node {
+        ... on Issue {
+          url
+          createdAt
+          repository {
+            ...repository
+          }
+        }
+        ... on PullRequest {
+          url
+          createdAt
+          repository {
+            ...repository
+          }
+        }
+        ... on Discussion {
+          url
+          createdAt
+          repository {
+            ...repository
+          }
+        }
+      }
+    }
+    issueCount
+    discussionCount
+  }
+}
+
+fragment repository on Repository {
+  nameWithOwner
+  url
+  stargazerCount
+  isPrivate
+}";
+
+impl GitHubClient {
+    /// Counts `username`'s contributions of each of `kinds` per repository,
+    /// sorted by the total count.
+    pub async fn get_contributions(
+        &self,
+        username: &str,
+        kinds: &[ContributionKind],
+        options: &ContributedReposOptions,
+    ) -> Result<Vec<RepoContributions>> {
+        let mut repos: HashMap<String, RepoContributions> = HashMap::new();
+
+        // One kind after the other, the search API allows only 30 requests per minute.
+        for &kind in kinds {
+            info!("Fetching {kind} contributions for {username}");
+
+            let body = json!({
+                "query": QUERY_CONTRIBUTIONS,
+                "variables": {
+                    "q": kind.search_query(username, options.include_private),
+                    "type": kind.search_type(),
+                    "perPage": PER_PAGE,
+                }
+            });
+            let contributions: Vec<Contribution> = self.search_all(body, &options.range).await?;
+
+            for contribution in contributions {
+                let repository = contribution.repository;
+                let repo = repos
+                    .entry(repository.name_with_owner.clone())
+                    .or_insert_with(|| RepoContributions {
+                        full_name: repository.name_with_owner,
+                        html_url: repository.url,
+                        stargazer_count: repository.stargazer_count,
+                        is_private: repository.is_private,
+                        counts: BTreeMap::new(),
+                        first_contribution_at: contribution.created_at,
+                        last_contribution_at: contribution.created_at,
+                    });
+                *repo.counts.entry(kind).or_default() += 1;
+                repo.first_contribution_at =
+                    repo.first_contribution_at.min(contribution.created_at);
+                repo.last_contribution_at = repo.last_contribution_at.max(contribution.created_at);
+            }
+        }
+
+        let mut repos: Vec<_> = repos.into_values().collect();
+        repos.sort_by_key(|repo| Reverse((repo.total(), repo.last_contribution_at)));
+
+        Ok(repos)
+    }
 }
 
 #[cfg(test)]
@@ -479,7 +1811,7 @@ mod tests {
 
     #[test]
     fn pull_request_search_result_accepts_null_edges() {
-        let result: PullRequestSearchResult = serde_json::from_value(json!({
+        let result: SearchResult<PullRequest> = serde_json::from_value(json!({
             "issueCount": 2,
             "pageInfo": {
                 "hasNextPage": false,
@@ -491,8 +1823,22 @@ mod tests {
                     "node": {
                         "url": "https://github.com/owner/repo/pull/1",
                         "createdAt": "2026-06-15T00:00:00Z",
+                        "state": "MERGED",
+                        "additions": 5,
+                        "deletions": 1,
+                        "changedFiles": 2,
+                        "commits": {
+                            "totalCount": 1,
+                        },
                         "repository": {
+                            "nameWithOwner": "owner/repo",
                             "stargazerCount": 42,
+                            "forkCount": 3,
+                            "primaryLanguage": { "name": "Rust" },
+                            "description": null,
+                            "isArchived": false,
+                            "isPrivate": false,
+                            "owner": { "avatarUrl": "https://avatars.githubusercontent.com/u/1" },
                         },
                     },
                 },
@@ -516,10 +1862,12 @@ mod tests {
                 {
                     "node": {
                         "nameWithOwner": "owner/repo",
+                        "url": "https://github.com/owner/repo",
                         "stargazerCount": 42,
                         "forkCount": 7,
                         "primaryLanguage": null,
                         "isArchived": false,
+                        "isPrivate": false,
                         "createdAt": "2026-06-15T00:00:00Z",
                         "pushedAt": null,
                     },
@@ -531,6 +1879,597 @@ mod tests {
         assert_eq!(result.edges.into_iter().flatten().count(), 1);
     }
 
+    #[test]
+    fn client_builder_requires_token() {
+        assert!(GitHubClient::builder().build().is_err());
+    }
+
+    #[test]
+    fn client_builder_derives_graphql_url_from_base_url() {
+        let client = GitHubClient::builder()
+            .token("token")
+            .base_url("https://ghe.example.com/api/")
+            .build()
+            .unwrap();
+        assert_eq!(client.graphql_url, "https://ghe.example.com/api/graphql");
+    }
+
+    #[test]
+    fn client_builder_derives_web_url_from_base_url() {
+        let client = GitHubClient::builder().token("token").build().unwrap();
+        assert_eq!(client.web_url(), "https://github.com");
+
+        let client = GitHubClient::builder()
+            .token("token")
+            .base_url("https://ghe.example.com/api")
+            .build()
+            .unwrap();
+        assert_eq!(client.web_url(), "https://ghe.example.com");
+
+        let client = GitHubClient::builder()
+            .token("token")
+            .base_url("https://ghe.example.com/api")
+            .web_url("https://code.example.com/")
+            .build()
+            .unwrap();
+        assert_eq!(client.web_url(), "https://code.example.com");
+    }
+
+    #[test]
+    fn repository_url_from_pull_request_url_keeps_host() {
+        assert_eq!(
+            repository_url_from_pull_request_url("https://ghe.example.com/owner/repo/pull/42"),
+            Some("https://ghe.example.com/owner/repo")
+        );
+        assert_eq!(
+            repository_url_from_pull_request_url("https://ghe.example.com/owner/repo/issues/42"),
+            None
+        );
+    }
+
+    #[test]
+    fn retry_policy_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3), Duration::from_secs(5));
+        assert_eq!(policy.backoff(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn rate_limit_prefers_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("30"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1800000000"));
+
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert!(rate_limit.is_exceeded());
+        assert_eq!(
+            rate_limit.wait(DateTime::from_timestamp(0, 0).unwrap()),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn secondary_rate_limit_without_headers_backs_off() {
+        let rate_limit = RateLimit::from_headers(&HeaderMap::new());
+        let now = DateTime::from_timestamp(0, 0).unwrap();
+        assert!(!rate_limit.is_exceeded());
+        assert_eq!(
+            rate_limit.or_secondary_backoff(now).wait(now),
+            Some(SECONDARY_RATE_LIMIT_BACKOFF)
+        );
+
+        assert!(is_secondary_rate_limit(
+            r#"{"message":"You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#
+        ));
+        assert!(is_secondary_rate_limit(
+            r#"{"message":"You have triggered an abuse detection mechanism."}"#
+        ));
+        assert!(!is_secondary_rate_limit(
+            r#"{"message":"Resource not accessible by personal access token"}"#
+        ));
+
+        // A wait GitHub did ask for wins.
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("5"));
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert_eq!(
+            rate_limit.or_secondary_backoff(now).wait(now),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn rate_limit_waits_until_reset_when_exhausted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1800000060"));
+
+        let rate_limit = RateLimit::from_headers(&headers);
+        let now = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+        assert_eq!(rate_limit.wait(now), Some(Duration::from_secs(60)));
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert!(!rate_limit.is_exceeded());
+        assert_eq!(rate_limit.wait(now), None);
+    }
+
+    #[test]
+    fn graphql_rate_limited_error_is_detected() {
+        assert!(is_graphql_rate_limited(&json!({
+            "errors": [{"type": "RATE_LIMITED", "message": "API rate limit exceeded"}],
+        })));
+        assert!(!is_graphql_rate_limited(&json!({
+            "errors": [{"type": "NOT_FOUND", "message": "Could not resolve"}],
+        })));
+        assert!(!is_graphql_rate_limited(&json!({ "data": {} })));
+    }
+
+    #[test]
+    fn check_owner_honours_requested_kind() {
+        let org = json!({
+            "data": { "repositoryOwner": { "__typename": "Organization" } },
+        });
+        assert!(check_owner(&org, "rust-lang", OwnerKind::Auto).is_ok());
+        assert!(check_owner(&org, "rust-lang", OwnerKind::Organization).is_ok());
+        assert!(matches!(
+            check_owner(&org, "rust-lang", OwnerKind::User),
+            Err(Error::NotAUser(login)) if login == "rust-lang"
+        ));
+
+        let user = json!({
+            "data": { "repositoryOwner": { "__typename": "User" } },
+        });
+        assert!(matches!(
+            check_owner(&user, "octo", OwnerKind::Organization),
+            Err(Error::NotAnOrganization(login)) if login == "octo"
+        ));
+
+        let missing = json!({
+            "data": { "repositoryOwner": null },
+            "errors": [{ "type": "NOT_FOUND", "message": "Could not resolve" }],
+        });
+        assert!(matches!(
+            check_owner(&missing, "nobody", OwnerKind::Auto),
+            Err(Error::UserNotFound(login)) if login == "nobody"
+        ));
+    }
+
+    #[test]
+    fn owner_kind_parses_aliases() {
+        assert_eq!("org".parse(), Ok(OwnerKind::Organization));
+        assert_eq!("user".parse(), Ok(OwnerKind::User));
+        assert!("team".parse::<OwnerKind>().is_err());
+    }
+
+    #[test]
+    fn check_graphql_errors_classifies_error_types() {
+        let resp = json!({
+            "data": null,
+            "errors": [{ "type": "INSUFFICIENT_SCOPES", "message": "missing read:org" }],
+        });
+        let err = check_graphql_errors(&resp).unwrap_err();
+        assert!(matches!(err, Error::Unauthorized(_)));
+        assert_eq!(err.status_code(), StatusCode::UNAUTHORIZED);
+
+        let resp = json!({
+            "data": { "search": { "issueCount": 1 } },
+            "errors": [{ "type": "SERVICE_UNAVAILABLE", "message": "timeout" }],
+        });
+        let err = check_graphql_errors(&resp).unwrap_err();
+        assert!(matches!(err, Error::PartialResults(_)));
+        assert_eq!(err.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+        assert!(check_graphql_errors(&json!({ "data": {} })).is_ok());
+    }
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "github-contrib-stats-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn pr_page(issue_count: u32, prs: &[(&str, &str, &str)]) -> Value {
+        let edges: Vec<_> = prs
+            .iter()
+            .map(|(url, created_at, state)| {
+                json!({
+                    "node": {
+                        "url": url,
+                        "createdAt": created_at,
+                        "state": state,
+                        "additions": 10,
+                        "deletions": 2,
+                        "changedFiles": 1,
+                        "commits": { "totalCount": 3 },
+                        "repository": {
+                            "nameWithOwner": repository_name_from_pull_request_url(url),
+                            "stargazerCount": 10,
+                            "forkCount": 2,
+                            "primaryLanguage": { "name": "Rust" },
+                            "description": "A test repository",
+                            "isArchived": false,
+                            "isPrivate": false,
+                            "owner": { "avatarUrl": "https://avatars.githubusercontent.com/u/1" },
+                        },
+                    },
+                })
+            })
+            .collect();
+        json!({
+            "data": {
+                "search": {
+                    "issueCount": issue_count,
+                    "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    "edges": edges,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn exchange_without_query_matches_on_variables() {
+        let exchange = Exchange {
+            request: json!({ "variables": { "q": "author:a" } }),
+            response: json!({}),
+        };
+        assert!(exchange.matches(&json!({ "query": "query", "variables": { "q": "author:a" } })));
+        assert!(!exchange.matches(&json!({ "query": "query", "variables": { "q": "author:b" } })));
+    }
+
+    #[tokio::test]
+    async fn replay_paginates_past_max_results() {
+        let dir = fixture_dir("replay-pagination");
+        let query = "author:octo type:pr is:public sort:created-desc -user:octo";
+        let save = |variables: Value, response: Value| {
+            Exchange {
+                request: json!({ "variables": variables }),
+                response,
+            }
+            .save(&dir)
+            .unwrap();
+        };
+
+        // First window: 1001 matches, so ten pages plus one more window.
+        save(
+            json!({ "q": query, "perPage": PER_PAGE, "cursor": null }),
+            pr_page(
+                1001,
+                &[
+                    (
+                        "https://github.com/rust-lang/rust/pull/3",
+                        "2026-03-01T00:00:00Z",
+                        "OPEN",
+                    ),
+                    (
+                        "https://github.com/tokio-rs/tokio/pull/1",
+                        "2025-01-01T00:00:00Z",
+                        "MERGED",
+                    ),
+                ],
+            ),
+        );
+        for offset in (100..1000).step_by(100) {
+            let cursor = BASE64_STANDARD.encode(format!("cursor:{offset}"));
+            let prs: &[(&str, &str, &str)] = if offset == 900 {
+                &[(
+                    "https://github.com/rust-lang/rust/pull/2",
+                    "2024-01-01T00:00:00Z",
+                    "CLOSED",
+                )]
+            } else {
+                &[]
+            };
+            save(
+                json!({ "q": query, "perPage": PER_PAGE, "cursor": cursor }),
+                pr_page(1001, prs),
+            );
+        }
+        // Second window, limited to PRs older than the last one seen.
+        save(
+            json!({
+                "q": format!("{query} created:<2024-01-01T00:00:00+00:00"),
+                "perPage": PER_PAGE,
+                "cursor": BASE64_STANDARD.encode("cursor:0"),
+            }),
+            pr_page(
+                1,
+                &[(
+                    "https://github.com/rust-lang/rust/pull/1",
+                    "2023-01-01T00:00:00Z",
+                    "MERGED",
+                )],
+            ),
+        );
+
+        let client = GitHubClient::builder().replay(&dir).build().unwrap();
+        let repos = client
+            .get_contributed_repos("octo", &ContributedReposOptions::default())
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].full_name, "rust-lang/rust");
+        assert_eq!(repos[0].pr_count, 3);
+        assert_eq!(
+            (
+                repos[0].merged_count,
+                repos[0].open_count,
+                repos[0].closed_count
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(repos[0].lines_changed(), 36);
+        assert_eq!(repos[0].language(), "Rust");
+        assert_eq!(repos[0].fork_count, 2);
+        assert_eq!(repos[0].commit_count, 9);
+        assert_eq!(
+            repos[0].first_pr.url,
+            "https://github.com/rust-lang/rust/pull/1"
+        );
+        assert_eq!(
+            repos[0].last_pr.url,
+            "https://github.com/rust-lang/rust/pull/3"
+        );
+        let dates: Vec<_> = repos[0]
+            .pr_dates
+            .iter()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .collect();
+        assert_eq!(dates, ["2023-01-01", "2024-01-01", "2026-03-01"]);
+        assert_eq!(repos[1].full_name, "tokio-rs/tokio");
+        assert_eq!(repos[1].pr_count, 1);
+    }
+
+    #[tokio::test]
+    async fn replay_created_repos_applies_include_options() {
+        let dir = fixture_dir("replay-created");
+        let repo = |name: &str, stars: u32, archived: bool| {
+            json!({
+                "node": {
+                    "nameWithOwner": format!("octo/{name}"),
+                    "url": format!("https://github.com/octo/{name}"),
+                    "stargazerCount": stars,
+                    "forkCount": 0,
+                    "primaryLanguage": null,
+                    "isArchived": archived,
+                    "isPrivate": false,
+                    "createdAt": "2026-01-01T00:00:00Z",
+                    "pushedAt": null,
+                },
+            })
+        };
+        Exchange {
+            request: json!({
+                "variables": {
+                    "login": "octo",
+                    "affiliations": ["OWNER"],
+                    "isFork": null,
+                    "privacy": "PUBLIC",
+                    "perPage": PER_PAGE,
+                    "after": null,
+                },
+            }),
+            response: json!({
+                "data": {
+                    "repositoryOwner": {
+                        "__typename": "User",
+                        "repositories": {
+                            "totalCount": 3,
+                            "pageInfo": { "hasNextPage": false, "endCursor": null },
+                            "edges": [
+                                repo("popular", 10, false),
+                                repo("old", 5, true),
+                                repo("scratch", 0, false),
+                            ],
+                        },
+                    },
+                },
+            }),
+        }
+        .save(&dir)
+        .unwrap();
+
+        let client = GitHubClient::builder().replay(&dir).build().unwrap();
+        let options = CreatedReposOptions {
+            include_forks: true,
+            include_archived: false,
+            include_unstarred: true,
+            ..CreatedReposOptions::default()
+        };
+        let repos = client.get_created_repos("octo", &options).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = repos.iter().map(Repository::name).collect();
+        assert_eq!(names, ["popular", "scratch"]);
+    }
+
+    #[tokio::test]
+    async fn replay_counts_contributions_per_kind() {
+        let dir = fixture_dir("replay-contributions");
+        let save = |kind: ContributionKind, total: &str, urls: &[(&str, &str)]| {
+            let edges: Vec<_> = urls
+                .iter()
+                .map(|(repo, created_at)| {
+                    json!({
+                        "node": {
+                            "url": format!("https://github.com/{repo}/issues/1"),
+                            "createdAt": created_at,
+                            "repository": {
+                                "nameWithOwner": repo,
+                                "url": format!("https://github.com/{repo}"),
+                                "stargazerCount": 10,
+                                "isPrivate": false,
+                            },
+                        },
+                    })
+                })
+                .collect();
+            Exchange {
+                request: json!({
+                    "variables": {
+                        "q": kind.search_query("octo", false),
+                        "type": kind.search_type(),
+                        "perPage": PER_PAGE,
+                        "cursor": null,
+                    },
+                }),
+                response: json!({
+                    "data": {
+                        "search": {
+                            "issueCount": 0,
+                            total: urls.len(),
+                            "pageInfo": { "hasNextPage": false, "endCursor": null },
+                            "edges": edges,
+                        },
+                    },
+                }),
+            }
+            .save(&dir)
+            .unwrap();
+        };
+        save(
+            ContributionKind::Review,
+            "issueCount",
+            &[
+                ("rust-lang/rust", "2026-02-01T00:00:00Z"),
+                ("rust-lang/rust", "2026-01-01T00:00:00Z"),
+                ("tokio-rs/tokio", "2025-01-01T00:00:00Z"),
+            ],
+        );
+        save(
+            ContributionKind::DiscussionAnswer,
+            "discussionCount",
+            &[("tokio-rs/tokio", "2024-01-01T00:00:00Z")],
+        );
+
+        let client = GitHubClient::builder().replay(&dir).build().unwrap();
+        let repos = client
+            .get_contributions(
+                "octo",
+                &[ContributionKind::Review, ContributionKind::DiscussionAnswer],
+                &ContributedReposOptions::default(),
+            )
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].full_name, "rust-lang/rust");
+        assert_eq!(repos[0].count(ContributionKind::Review), 2);
+        assert_eq!(repos[1].full_name, "tokio-rs/tokio");
+        assert_eq!(repos[1].count(ContributionKind::Review), 1);
+        assert_eq!(repos[1].count(ContributionKind::DiscussionAnswer), 1);
+        assert_eq!(repos[1].count(ContributionKind::Issue), 0);
+        assert_eq!(
+            repos[1].first_contribution_at.to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn parse_date_bound_accepts_dates_years_and_relative_times() {
+        let now = DateTime::parse_from_rfc3339("2026-10-17T15:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let parse = |value, end| parse_date_bound(value, now, end).unwrap().to_rfc3339();
+
+        assert_eq!(parse("2026-03-01", false), "2026-03-01T00:00:00+00:00");
+        assert_eq!(parse("2026-03-01", true), "2026-03-01T23:59:59+00:00");
+        assert_eq!(parse("2025", false), "2025-01-01T00:00:00+00:00");
+        assert_eq!(parse("2025", true), "2025-12-31T23:59:59+00:00");
+        assert_eq!(parse("2w", false), "2026-10-03T00:00:00+00:00");
+        assert_eq!(parse("1y", false), "2025-10-17T00:00:00+00:00");
+        assert_eq!(
+            parse("2026-01-01T08:00:00+08:00", false),
+            "2026-01-01T00:00:00+00:00"
+        );
+        assert!(parse_date_bound("yesterday", now, false).is_err());
+        assert!(parse_date_bound("", now, false).is_err());
+        assert!(parse_date_bound("1年", now, false).is_err());
+        assert!(parse_date_bound("1é", now, false).is_err());
+    }
+
+    #[test]
+    fn date_range_builds_search_qualifiers() {
+        let range = DateRange::parse(Some("2026"), None).unwrap();
+        assert_eq!(
+            range.search_qualifier().as_deref(),
+            Some("created:2026-01-01T00:00:00+00:00..*")
+        );
+        let end = DateTime::parse_from_rfc3339("2026-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            range.search_qualifier_before(end),
+            "created:2026-01-01T00:00:00+00:00..2026-05-31T23:59:59+00:00"
+        );
+        assert_eq!(
+            DateRange::default().search_qualifier_before(end),
+            "created:<2026-06-01T00:00:00+00:00"
+        );
+        assert_eq!(DateRange::default().search_qualifier(), None);
+        assert!(DateRange::parse(Some("2026"), Some("2025")).is_err());
+    }
+
+    #[test]
+    fn date_range_keeps_repos_active_within_it() {
+        let range = DateRange::parse(Some("2025"), Some("2025")).unwrap();
+        let at = |value| {
+            DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        assert!(range.overlaps(at("2020-01-01T00:00:00Z"), Some(at("2025-06-01T00:00:00Z"))));
+        assert!(range.overlaps(at("2025-06-01T00:00:00Z"), None));
+        assert!(!range.overlaps(at("2020-01-01T00:00:00Z"), Some(at("2024-06-01T00:00:00Z"))));
+        assert!(!range.overlaps(at("2026-01-01T00:00:00Z"), Some(at("2026-06-01T00:00:00Z"))));
+    }
+
+    #[test]
+    fn search_query_drops_public_qualifier_for_private_contributions() {
+        assert_eq!(
+            ContributionKind::Issue.search_query("octo", false),
+            "author:octo type:issue is:public sort:created-desc -user:octo"
+        );
+        assert_eq!(
+            ContributionKind::Issue.search_query("octo", true),
+            "author:octo type:issue sort:created-desc -user:octo"
+        );
+    }
+
+    #[test]
+    fn contribution_kind_parses_aliases() {
+        assert_eq!("pull_request".parse(), Ok(ContributionKind::PullRequest));
+        assert_eq!("answer".parse(), Ok(ContributionKind::DiscussionAnswer));
+        assert!("commit".parse::<ContributionKind>().is_err());
+    }
+
+    #[tokio::test]
+    async fn replay_reports_missing_fixture() {
+        let dir = fixture_dir("replay-missing");
+        let client = GitHubClient::builder().replay(&dir).build().unwrap();
+        let err = client
+            .get_contributed_repos("octo", &ContributedReposOptions::default())
+            .await
+            .unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(err, Error::Fixture(_)));
+    }
+
     #[test]
     fn repository_name_from_pull_request_url_extracts_owner_and_repo() {
         assert_eq!(
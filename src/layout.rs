@@ -0,0 +1,108 @@
+//! Column widths of the SVG tables, sized to their content.
+
+/// How wide a column of a table would like to be, in pixels.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ColumnSize {
+    /// Fits every cell without truncating it.
+    pub natural: i32,
+    /// The column doesn't shrink below this, truncating its cells instead.
+    pub min: i32,
+}
+
+impl ColumnSize {
+    /// A column of text, which may be truncated down to `min`.
+    #[must_use]
+    pub fn text(natural: i32, min: i32) -> Self {
+        Self {
+            natural,
+            min: min.min(natural),
+        }
+    }
+
+    /// A column that doesn't shrink, such as one of numbers or dates.
+    #[must_use]
+    pub fn fixed(width: i32) -> Self {
+        Self {
+            natural: width,
+            min: width,
+        }
+    }
+}
+
+/// Splits `width` between `columns`, in order.
+///
+/// Room to spare is spread in proportion to the natural widths. Otherwise the
+/// columns above their minimum shrink in proportion to how much they can, and
+/// only when that isn't enough does every column shrink below its minimum.
+/// The widths always add up to `width`.
+#[must_use]
+pub fn fit_columns(columns: &[ColumnSize], width: i32) -> Vec<i32> {
+    let natural: i32 = columns.iter().map(|column| column.natural).sum();
+    let min: i32 = columns.iter().map(|column| column.min).sum();
+    let width = width.max(0);
+
+    let widths: Vec<f64> = if natural <= width {
+        let scale = f64::from(width) / f64::from(natural.max(1));
+        columns
+            .iter()
+            .map(|column| f64::from(column.natural) * scale)
+            .collect()
+    } else if min <= width {
+        let shrink = f64::from(natural - width) / f64::from(natural - min);
+        columns
+            .iter()
+            .map(|column| {
+                f64::from(column.natural) - f64::from(column.natural - column.min) * shrink
+            })
+            .collect()
+    } else {
+        let scale = f64::from(width) / f64::from(min);
+        columns
+            .iter()
+            .map(|column| f64::from(column.min) * scale)
+            .collect()
+    };
+
+    // Rounds the column edges rather than the widths, so no pixel is lost.
+    let mut end = 0.0;
+    let mut x = 0;
+    widths
+        .into_iter()
+        .map(|width| {
+            end += width;
+            let next = end.round() as i32;
+            let width = next - x;
+            x = next;
+            width
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_columns_grows_and_shrinks_to_width() {
+        let columns = [
+            ColumnSize::fixed(50),
+            ColumnSize::text(300, 100),
+            ColumnSize::fixed(100),
+        ];
+
+        // Spare room goes to every column.
+        assert_eq!(fit_columns(&columns, 900), vec![100, 600, 200]);
+        assert_eq!(fit_columns(&columns, 450), vec![50, 300, 100]);
+
+        // Only text shrinks, down to its minimum.
+        assert_eq!(fit_columns(&columns, 350), vec![50, 200, 100]);
+        assert_eq!(fit_columns(&columns, 250), vec![50, 100, 100]);
+
+        // Then everything does, still adding up to the width.
+        let widths = fit_columns(&columns, 125);
+        assert_eq!(widths, vec![25, 50, 50]);
+        assert_eq!(fit_columns(&columns, 333).iter().sum::<i32>(), 333);
+
+        assert!(fit_columns(&[], 100).is_empty());
+    }
+}
@@ -1,2 +1,7 @@
+pub mod font;
 pub mod github;
+pub mod layout;
+pub mod query;
 pub mod render;
+pub mod stats;
+pub mod theme;
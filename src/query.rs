@@ -0,0 +1,571 @@
+//! Narrowing fetched repositories down to the ones a card lists.
+
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Utc};
+
+use crate::github::{ContributedRepo, PullRequest, RepoContributions, Repository};
+
+/// Which repositories a card lists, and in which order.
+///
+/// The fetch functions return every repository, so applying the same query
+/// to them gives the same rows wherever the card is rendered: repositories
+/// are filtered first, then sorted, then limited to `max_repos`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StatsQuery {
+    pub filter: RepoFilter,
+    /// Minimum counts, ignored for entries without such a count, e.g. PRs of created repos.
+    pub min_stars: u32,
+    pub min_forks: u32,
+    pub min_prs: u32,
+    pub min_merged: u32,
+    /// Order other than the one repositories were fetched in.
+    pub sort: Option<RepoOrder>,
+    pub max_repos: Option<usize>,
+}
+
+impl StatsQuery {
+    /// A query that only keeps the first `max_repos` repositories.
+    #[must_use]
+    pub fn with_max_repos(max_repos: Option<usize>) -> Self {
+        Self {
+            max_repos,
+            ..Self::default()
+        }
+    }
+
+    /// The query keeping every repository the filter allows, for totals over all of them.
+    #[must_use]
+    pub fn filter_only(&self) -> Self {
+        Self {
+            filter: self.filter.clone(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn apply<T: RepoEntry>(&self, mut repos: Vec<T>) -> Vec<T> {
+        repos.retain(|repo| self.keeps(repo));
+        if let Some(order) = self.sort {
+            order.sort(&mut repos);
+        }
+        if let Some(max_repos) = self.max_repos {
+            repos.truncate(max_repos);
+        }
+        repos
+    }
+
+    fn keeps(&self, repo: &impl RepoEntry) -> bool {
+        let at_least = |key, min| match repo.sort_value(key) {
+            SortValue::Count(Some(count)) => count >= min,
+            _ => true,
+        };
+        at_least(SortKey::Stars, self.min_stars)
+            && at_least(SortKey::Forks, self.min_forks)
+            && at_least(SortKey::PrCount, self.min_prs)
+            && at_least(SortKey::Merged, self.min_merged)
+            && self.filter.allows(repo.full_name(), repo.language_name())
+    }
+}
+
+/// Which repositories a listing keeps.
+///
+/// A repository is kept if it matches any include pattern, or there are none,
+/// and no exclude pattern.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RepoFilter {
+    pub include: Vec<RepoPattern>,
+    pub exclude: Vec<RepoPattern>,
+}
+
+impl RepoFilter {
+    /// Parses comma separated lists of patterns, see [`RepoPattern`].
+    pub fn parse(include: Option<&str>, exclude: Option<&str>) -> Result<Self, String> {
+        let patterns = |value: Option<&str>| {
+            value
+                .into_iter()
+                .flat_map(|value| value.split(','))
+                .filter(|pattern| !pattern.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: patterns(include)?,
+            exclude: patterns(exclude)?,
+        })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether the repository `full_name` (`owner/name`) written in `language` is kept.
+    #[must_use]
+    pub fn allows(&self, full_name: &str, language: Option<&str>) -> bool {
+        let matches = |pattern: &RepoPattern| pattern.matches(full_name, language);
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Formats as space separated patterns, with `!` before exclude patterns.
+impl std::fmt::Display for RepoFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let include = self.include.iter().map(ToString::to_string);
+        let exclude = self.exclude.iter().map(|pattern| format!("!{pattern}"));
+        f.write_str(&include.chain(exclude).collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// A case-insensitive pattern on a repository.
+///
+/// Parsed from `lang:<language>`, from a glob on `owner/name` such as
+/// `j178/dotfiles` or `*/awesome-*`, or from a glob on the owner alone.
+/// Globs support `*` and `?`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum RepoPattern {
+    Repo(String),
+    Owner(String),
+    Language(String),
+}
+
+impl RepoPattern {
+    /// Language patterns never match a repository without a known `language`.
+    #[must_use]
+    pub fn matches(&self, full_name: &str, language: Option<&str>) -> bool {
+        match self {
+            RepoPattern::Repo(glob) => glob_matches(glob, &full_name.to_lowercase()),
+            RepoPattern::Owner(glob) => {
+                let owner = full_name
+                    .split_once('/')
+                    .map_or(full_name, |(owner, _)| owner);
+                glob_matches(glob, &owner.to_lowercase())
+            }
+            RepoPattern::Language(name) => language.is_some_and(|l| l.to_lowercase() == *name),
+        }
+    }
+}
+
+impl std::fmt::Display for RepoPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoPattern::Repo(glob) | RepoPattern::Owner(glob) => f.write_str(glob),
+            RepoPattern::Language(name) => write!(f, "lang:{name}"),
+        }
+    }
+}
+
+impl std::str::FromStr for RepoPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = s.trim().to_lowercase();
+        if let Some(language) = pattern.strip_prefix("lang:") {
+            let language = language.trim();
+            if language.is_empty() {
+                return Err(format!("missing language in pattern '{s}'"));
+            }
+            return Ok(RepoPattern::Language(language.to_string()));
+        }
+        match pattern.split('/').count() {
+            _ if pattern.is_empty() => Err("empty repository pattern".to_string()),
+            1 => Ok(RepoPattern::Owner(pattern)),
+            2 => Ok(RepoPattern::Repo(pattern)),
+            _ => Err(format!(
+                "invalid repository pattern '{s}', expected owner, owner/name or lang:language"
+            )),
+        }
+    }
+}
+
+/// Matches `text` against `glob`, where `*` matches any run of characters and `?` one.
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut g, mut t) = (0, 0);
+    // Where to resume after the last `*` if the rest does not match.
+    let mut backtrack = None;
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, start)) => {
+                    g = star + 1;
+                    t = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// What repositories can be ordered by.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SortKey {
+    Stars,
+    Forks,
+    /// The last push to a created repo, or the last contribution.
+    Updated,
+    /// When a created repo was created, or the first contribution.
+    Created,
+    /// The number of PRs, or of contributions of all kinds.
+    PrCount,
+    Merged,
+    Name,
+}
+
+impl SortKey {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortKey::Stars => "stars",
+            SortKey::Forks => "forks",
+            SortKey::Updated => "updated",
+            SortKey::Created => "created",
+            SortKey::PrCount => "prs",
+            SortKey::Merged => "merged",
+            SortKey::Name => "name",
+        }
+    }
+
+    /// Names sort A to Z by default, everything else largest or newest first.
+    #[must_use]
+    pub fn default_direction(self) -> SortDirection {
+        match self {
+            SortKey::Name => SortDirection::Ascending,
+            _ => SortDirection::Descending,
+        }
+    }
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stars" => Ok(SortKey::Stars),
+            "forks" => Ok(SortKey::Forks),
+            "updated" | "pushed" | "last_pr" => Ok(SortKey::Updated),
+            "created" | "first_pr" => Ok(SortKey::Created),
+            "prs" | "pr_count" => Ok(SortKey::PrCount),
+            "merged" => Ok(SortKey::Merged),
+            "name" => Ok(SortKey::Name),
+            _ => Err(format!(
+                "unknown sort key '{s}', expected stars, forks, updated, created, prs, merged or name"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        }
+    }
+}
+
+impl std::str::FromStr for SortDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "asc" => Ok(SortDirection::Ascending),
+            "desc" => Ok(SortDirection::Descending),
+            _ => Err(format!(
+                "unknown sort direction '{s}', expected asc or desc"
+            )),
+        }
+    }
+}
+
+/// An ordering of repositories, parsed from `key` or `key:asc`/`key:desc`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct RepoOrder {
+    pub key: SortKey,
+    pub direction: SortDirection,
+}
+
+impl RepoOrder {
+    #[must_use]
+    pub fn new(key: SortKey) -> Self {
+        Self {
+            key,
+            direction: key.default_direction(),
+        }
+    }
+
+    /// Sorts `repos` stably, so ties keep their current order.
+    pub fn sort<T: RepoEntry>(&self, repos: &mut [T]) {
+        match self.direction {
+            SortDirection::Ascending => repos.sort_by_cached_key(|repo| repo.sort_value(self.key)),
+            SortDirection::Descending => {
+                repos.sort_by_cached_key(|repo| Reverse(repo.sort_value(self.key)));
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RepoOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.key, self.direction.as_str())
+    }
+}
+
+impl std::str::FromStr for RepoOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once(':') {
+            Some((key, direction)) => Ok(Self {
+                key: key.parse()?,
+                direction: direction.parse()?,
+            }),
+            None => Ok(Self::new(s.trim().parse()?)),
+        }
+    }
+}
+
+/// A value a repository is sorted by, see [`RepoEntry`].
+///
+/// Counts and times a kind of entry does not have are `None`, and sort
+/// before all others.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum SortValue {
+    Count(Option<u32>),
+    Time(Option<DateTime<Utc>>),
+    /// Lowercased so that names compare case-insensitively.
+    Text(String),
+}
+
+/// A row of a repository listing, which a [`StatsQuery`] filters and orders.
+pub trait RepoEntry {
+    /// The `owner/name` of the repository.
+    fn full_name(&self) -> &str;
+    fn language_name(&self) -> Option<&str>;
+    fn sort_value(&self, key: SortKey) -> SortValue;
+}
+
+impl RepoEntry for Repository {
+    fn full_name(&self) -> &str {
+        &self.name_with_owner
+    }
+
+    fn language_name(&self) -> Option<&str> {
+        self.primary_language.as_ref().map(|l| l.name.as_str())
+    }
+
+    fn sort_value(&self, key: SortKey) -> SortValue {
+        match key {
+            SortKey::Stars => SortValue::Count(Some(self.stargazer_count)),
+            SortKey::Forks => SortValue::Count(Some(self.fork_count)),
+            SortKey::Updated => SortValue::Time(self.pushed_at),
+            SortKey::Created => SortValue::Time(Some(self.created_at)),
+            SortKey::PrCount | SortKey::Merged => SortValue::Count(None),
+            SortKey::Name => SortValue::Text(self.name_with_owner.to_lowercase()),
+        }
+    }
+}
+
+impl RepoEntry for ContributedRepo {
+    fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    fn language_name(&self) -> Option<&str> {
+        self.primary_language.as_ref().map(|l| l.name.as_str())
+    }
+
+    fn sort_value(&self, key: SortKey) -> SortValue {
+        match key {
+            SortKey::Stars => SortValue::Count(Some(self.stargazer_count)),
+            SortKey::Forks => SortValue::Count(Some(self.fork_count)),
+            SortKey::Updated => SortValue::Time(Some(self.last_pr.created_at)),
+            SortKey::Created => SortValue::Time(Some(self.first_pr.created_at)),
+            SortKey::PrCount => SortValue::Count(Some(self.pr_count)),
+            SortKey::Merged => SortValue::Count(Some(self.merged_count)),
+            SortKey::Name => SortValue::Text(self.full_name.to_lowercase()),
+        }
+    }
+}
+
+impl RepoEntry for RepoContributions {
+    fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    /// Issues, reviews and answers are fetched without the repository language.
+    fn language_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn sort_value(&self, key: SortKey) -> SortValue {
+        match key {
+            SortKey::Stars => SortValue::Count(Some(self.stargazer_count)),
+            SortKey::Forks | SortKey::Merged => SortValue::Count(None),
+            SortKey::Updated => SortValue::Time(Some(self.last_contribution_at)),
+            SortKey::Created => SortValue::Time(Some(self.first_contribution_at)),
+            SortKey::PrCount => SortValue::Count(Some(self.total())),
+            SortKey::Name => SortValue::Text(self.full_name.to_lowercase()),
+        }
+    }
+}
+
+impl RepoEntry for PullRequest {
+    fn full_name(&self) -> &str {
+        &self.repository.name_with_owner
+    }
+
+    fn language_name(&self) -> Option<&str> {
+        self.repository
+            .primary_language
+            .as_ref()
+            .map(|l| l.name.as_str())
+    }
+
+    fn sort_value(&self, key: SortKey) -> SortValue {
+        match key {
+            SortKey::Stars => SortValue::Count(Some(self.repository.stargazer_count)),
+            SortKey::Forks => SortValue::Count(Some(self.repository.fork_count)),
+            SortKey::Updated | SortKey::Created => SortValue::Time(Some(self.created_at)),
+            SortKey::PrCount | SortKey::Merged => SortValue::Count(None),
+            SortKey::Name => SortValue::Text(self.repository.name_with_owner.to_lowercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_filter_matches_names_owners_and_languages() {
+        let filter =
+            RepoFilter::parse(Some("j178/*,rust-lang"), Some("*/dotfiles,lang:Python")).unwrap();
+        assert!(filter.allows("j178/github-contrib-stats", Some("Rust")));
+        assert!(filter.allows("Rust-Lang/rust", None));
+        assert!(!filter.allows("j178/dotfiles", Some("Shell")));
+        assert!(!filter.allows("j178/scripts", Some("Python")));
+        assert!(!filter.allows("octo/hello", Some("Rust")));
+        assert_eq!(
+            filter.to_string(),
+            "j178/* rust-lang !*/dotfiles !lang:python"
+        );
+
+        assert!(RepoFilter::default().allows("octo/hello", None));
+        assert!(glob_matches("a*b?d", "axxbcd"));
+        assert!(!glob_matches("a*b?d", "axxbd"));
+        assert!(glob_matches("*项目", "用户/项目"));
+        assert!("a/b/c".parse::<RepoPattern>().is_err());
+        assert!("lang:".parse::<RepoPattern>().is_err());
+    }
+
+    #[test]
+    fn repo_order_sorts_by_key_and_direction() {
+        let repo = |name: &str, stars: u32, pushed_at: Option<&str>| Repository {
+            name_with_owner: name.to_string(),
+            url: String::new(),
+            stargazer_count: stars,
+            fork_count: 0,
+            primary_language: None,
+            description: None,
+            is_archived: false,
+            is_private: false,
+            created_at: DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            pushed_at: pushed_at
+                .map(|t| DateTime::parse_from_rfc3339(t).unwrap().with_timezone(&Utc)),
+        };
+        let mut repos = vec![
+            repo("octo/b", 5, None),
+            repo("Octo/C", 5, Some("2026-01-01T00:00:00Z")),
+            repo("octo/a", 1, Some("2025-01-01T00:00:00Z")),
+        ];
+        let names = |repos: &[Repository]| {
+            repos
+                .iter()
+                .map(|repo| repo.name_with_owner.clone())
+                .collect::<Vec<_>>()
+        };
+
+        "name".parse::<RepoOrder>().unwrap().sort(&mut repos);
+        assert_eq!(names(&repos), ["octo/a", "octo/b", "Octo/C"]);
+        "updated".parse::<RepoOrder>().unwrap().sort(&mut repos);
+        assert_eq!(names(&repos), ["Octo/C", "octo/a", "octo/b"]);
+        // Ties keep their previous order.
+        "stars:asc".parse::<RepoOrder>().unwrap().sort(&mut repos);
+        assert_eq!(names(&repos), ["octo/a", "Octo/C", "octo/b"]);
+
+        assert_eq!(
+            "pushed:desc".parse::<RepoOrder>().unwrap().to_string(),
+            "updated:desc"
+        );
+        assert!("stars:up".parse::<RepoOrder>().is_err());
+        assert!("size".parse::<RepoOrder>().is_err());
+    }
+
+    #[test]
+    fn stats_query_filters_then_sorts_then_limits() {
+        let repo = |name: &str, stars: u32, forks: u32| Repository {
+            name_with_owner: name.to_string(),
+            url: String::new(),
+            stargazer_count: stars,
+            fork_count: forks,
+            primary_language: None,
+            description: None,
+            is_archived: false,
+            is_private: false,
+            created_at: DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            pushed_at: None,
+        };
+        let repos = vec![
+            repo("octo/a", 100, 1),
+            repo("octo/b", 10, 50),
+            repo("octo/c", 60, 5),
+            repo("octo/d", 80, 2),
+            repo("octo/dotfiles", 90, 0),
+        ];
+        let query = StatsQuery {
+            filter: RepoFilter::parse(None, Some("*/dotfiles")).unwrap(),
+            min_stars: 50,
+            // Created repos have no PRs, so this keeps them all.
+            min_prs: 5,
+            sort: Some("name:desc".parse().unwrap()),
+            max_repos: Some(2),
+            ..StatsQuery::default()
+        };
+        let names: Vec<_> = query
+            .apply(repos)
+            .into_iter()
+            .map(|repo| repo.name_with_owner)
+            .collect();
+        assert_eq!(names, ["octo/d", "octo/c"]);
+    }
+}
@@ -1,14 +1,25 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 use prettytable::format::TableFormat;
-use prettytable::{Table, row};
+use prettytable::{Cell, Row, Table, row};
 
-use crate::github::{ContributedRepo, Repository};
+use crate::font;
+use crate::github::{
+    ContributedRepo, ContributionKind, PullRequest, RepoContributions, Repository,
+};
+use crate::layout::{ColumnSize, fit_columns};
+use crate::stats::{ContributionStats, LanguageWeight, Streak, Timeline, language_breakdown};
+use crate::theme::{Theme, ThemeColor};
 
 use svg::Document;
-use svg::node::element::{Anchor, Definitions, Group, Path, Rectangle, TSpan, Text};
+use svg::Node;
+use svg::node::element::{
+    Anchor, Circle, ClipPath, Definitions, Group, Path, Rectangle, Style, TSpan, Text,
+};
 
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 
 static MARKDOWN_TABLE: LazyLock<TableFormat> = LazyLock::new(|| {
     prettytable::format::FormatBuilder::new()
@@ -22,13 +33,19 @@ static MARKDOWN_TABLE: LazyLock<TableFormat> = LazyLock::new(|| {
         .build()
 });
 
-const SVG_WIDTH: i32 = 780;
+const ROW_HEIGHT: i32 = 40;
+const COMPACT_ROW_HEIGHT: i32 = 28;
+/// Space between the widest cell of a table column and the next column.
+const COLUMN_GAP: i32 = 20;
 const STATS_HEADER_HEIGHT: i32 = 56;
 const STATS_HEADER_ICON_SIZE: i32 = 20;
 const STATS_HEADER_CONTENT_OFFSET_Y: i32 = 4;
 const STATS_HEADER_TEXT_DY: &str = "0.35em";
 const STATS_HEADER_TEXT_GAP: i32 = 4;
 const STATS_FOOTER_HEIGHT: i32 = 26;
+/// The Linguist colour of languages without one of their own, and of the "Other" bucket.
+const OTHER_LANGUAGE_COLOR: &str = "#8B949E";
+const DEFAULT_WEB_URL: &str = "https://github.com";
 const GENERATOR_URL: &str = "http://github-contrib-stats.vercel.app/";
 const REPO_ICON_PATH: &str = "M2 2.5A2.5 2.5 0 0 1 4.5 0h8.75a.75.75 0 0 1 .75.75v12.5a.75.75 0 0 1-.75.75h-2.5a.75.75 0 0 1 0-1.5h1.75v-2h-8a1 1 0 0 0-.714 1.7.75.75 0 1 1-1.072 1.05A2.495 2.495 0 0 1 2 11.5Zm10.5-1h-8a1 1 0 0 0-1 1v6.708A2.486 2.486 0 0 1 4.5 9h8ZM5 12.25a.25.25 0 0 1 .25-.25h3.5a.25.25 0 0 1 .25.25v3.25a.25.25 0 0 1-.4.2l-1.45-1.087a.249.249 0 0 0-.3 0L5.4 15.7a.25.25 0 0 1-.4-.2Z";
 const PULL_REQUEST_ICON_PATH: &str = "M1.5 3.25a2.25 2.25 0 1 1 3 2.122v5.256a2.251 2.251 0 1 1-1.5 0V5.372A2.25 2.25 0 0 1 1.5 3.25Zm5.677-.177L9.573.677A.25.25 0 0 1 10 .854V2.5h1A2.5 2.5 0 0 1 13.5 5v5.628a2.251 2.251 0 1 1-1.5 0V5a1 1 0 0 0-1-1h-1v1.646a.25.25 0 0 1-.427.177L7.177 3.427a.25.25 0 0 1 0-.354ZM3.75 2.5a.75.75 0 1 0 0 1.5.75.75 0 0 0 0-1.5Zm0 9.5a.75.75 0 1 0 0 1.5.75.75 0 0 0 0-1.5Zm8.25.75a.75.75 0 1 0 1.5 0 .75.75 0 0 0-1.5 0Z";
@@ -41,53 +58,801 @@ pub trait Render {
         repos: &[ContributedRepo],
         author: &str,
     );
+    /// Renders one count column per kind, a single kind makes a card of its own.
+    fn render_contributions(
+        &self,
+        output: &mut String,
+        repos: &[RepoContributions],
+        kinds: &[ContributionKind],
+        author: &str,
+    );
+    /// Renders the headline numbers of `stats`, then PRs per year.
+    fn render_summary(&self, output: &mut String, stats: &ContributionStats, author: &str);
+}
+
+/// Label and value of each headline number in a summary.
+fn summary_rows(stats: &ContributionStats) -> Vec<(&'static str, String)> {
+    let streak = |streak: Option<Streak>| {
+        streak.map_or("None".to_string(), |streak| {
+            let months = if streak.months == 1 {
+                "month"
+            } else {
+                "months"
+            };
+            format!(
+                "{} {months} ({} to {})",
+                streak.months, streak.start, streak.end
+            )
+        })
+    };
+    let languages: Vec<_> = stats
+        .top_languages
+        .iter()
+        .take(5)
+        .map(|(name, prs)| format!("{name} ({prs})"))
+        .collect();
+    vec![
+        (
+            "Pull requests",
+            format!("{} ({} merged)", stats.pr_count, stats.merged_count),
+        ),
+        ("Repositories contributed to", stats.repo_count.to_string()),
+        (
+            "First contribution",
+            stats
+                .first_contribution_at
+                .map_or("N/A".to_string(), |t| t.format("%Y-%m-%d").to_string()),
+        ),
+        ("Longest streak", streak(stats.longest_streak)),
+        ("Current streak", streak(stats.current_streak)),
+        (
+            "Top languages",
+            if languages.is_empty() {
+                "N/A".to_string()
+            } else {
+                languages.join(", ")
+            },
+        ),
+        (
+            "Repositories created",
+            format!(
+                "{} ({} stars, {} forks)",
+                stats.created_repo_count, stats.created_star_count, stats.created_fork_count
+            ),
+        ),
+    ]
+}
+
+fn kind_title(kinds: &[ContributionKind]) -> &'static str {
+    match kinds {
+        [ContributionKind::PullRequest] => "Repositories contributed to",
+        [ContributionKind::Issue] => "Issues opened",
+        [ContributionKind::Review] => "Pull requests reviewed",
+        [ContributionKind::DiscussionAnswer] => "Discussions answered",
+        _ => "Contributions",
+    }
+}
+
+/// Where the contributions of `author` of this kind to the repository are listed.
+fn kind_search_url(kind: ContributionKind, html_url: &str, author: &str) -> String {
+    if html_url.is_empty() {
+        return String::new();
+    }
+    match kind {
+        ContributionKind::PullRequest => format!("{html_url}/pulls?q=is%3Apr+author%3A{author}"),
+        ContributionKind::Issue => format!("{html_url}/issues?q=is%3Aissue+author%3A{author}"),
+        ContributionKind::Review => {
+            format!("{html_url}/pulls?q=is%3Apr+reviewed-by%3A{author}+-author%3A{author}")
+        }
+        ContributionKind::DiscussionAnswer => {
+            format!("{html_url}/discussions?discussions_q=answered-by%3A{author}")
+        }
+    }
+}
+
+/// Lists `author`'s PRs in the repository, narrowed down by `filter`.
+fn pulls_url(html_url: &str, filter: &str, author: &str) -> String {
+    if html_url.is_empty() {
+        return String::new();
+    }
+    format!("{html_url}/pulls?q=is%3Apr+{filter}author%3A{author}")
+}
+
+/// Rows of anonymized private repositories have no URL and get plain text.
+fn markdown_link(text: impl std::fmt::Display, url: &str) -> String {
+    if url.is_empty() {
+        text.to_string()
+    } else {
+        format!("[{text}]({url})")
+    }
+}
+
+/// How renderers show the private repositories a token could see.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PrivateRepos {
+    /// List them like public repositories.
+    #[default]
+    Show,
+    /// Merge them into one unlinked row that only tells how many there are.
+    Anonymize,
+}
+
+impl PrivateRepos {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrivateRepos::Show => "show",
+            PrivateRepos::Anonymize => "anonymize",
+        }
+    }
+
+    fn created(self, repos: &[Repository]) -> Cow<'_, [Repository]> {
+        self.merge(
+            repos,
+            |repo| repo.is_private,
+            |private| {
+                let newest = private.iter().max_by_key(|repo| repo.pushed_at)?;
+                Some(Repository {
+                    name_with_owner: private_repos_label(private.len()),
+                    url: String::new(),
+                    stargazer_count: private.iter().map(|x| x.stargazer_count).sum(),
+                    fork_count: private.iter().map(|x| x.fork_count).sum(),
+                    primary_language: None,
+                    description: None,
+                    is_archived: false,
+                    is_private: true,
+                    created_at: private.iter().map(|x| x.created_at).min()?,
+                    pushed_at: newest.pushed_at,
+                })
+            },
+        )
+    }
+
+    fn contributed(self, repos: &[ContributedRepo]) -> Cow<'_, [ContributedRepo]> {
+        self.merge(
+            repos,
+            |repo| repo.is_private,
+            |private| {
+                let sum = |f: fn(&ContributedRepo) -> u32| private.iter().map(f).sum();
+                let without_url = |pr: &PullRequest| PullRequest {
+                    url: String::new(),
+                    ..pr.clone()
+                };
+                Some(ContributedRepo {
+                    full_name: private_repos_label(private.len()),
+                    html_url: String::new(),
+                    stargazer_count: sum(|x| x.stargazer_count),
+                    fork_count: sum(|x| x.fork_count),
+                    primary_language: None,
+                    description: None,
+                    is_archived: false,
+                    is_private: true,
+                    owner_avatar_url: String::new(),
+                    pr_count: sum(|x| x.pr_count),
+                    merged_count: sum(|x| x.merged_count),
+                    open_count: sum(|x| x.open_count),
+                    closed_count: sum(|x| x.closed_count),
+                    additions: sum(|x| x.additions),
+                    deletions: sum(|x| x.deletions),
+                    changed_files: sum(|x| x.changed_files),
+                    commit_count: sum(|x| x.commit_count),
+                    first_pr: without_url(
+                        &private
+                            .iter()
+                            .min_by_key(|x| x.first_pr.created_at)?
+                            .first_pr,
+                    ),
+                    last_pr: without_url(
+                        &private.iter().max_by_key(|x| x.last_pr.created_at)?.last_pr,
+                    ),
+                    pr_dates: {
+                        let mut dates: Vec<_> =
+                            private.iter().flat_map(|x| x.pr_dates.clone()).collect();
+                        dates.sort();
+                        dates
+                    },
+                })
+            },
+        )
+    }
+
+    fn contributions(self, repos: &[RepoContributions]) -> Cow<'_, [RepoContributions]> {
+        self.merge(
+            repos,
+            |repo| repo.is_private,
+            |private| {
+                let mut counts = BTreeMap::new();
+                for (&kind, &count) in private.iter().flat_map(|x| &x.counts) {
+                    *counts.entry(kind).or_default() += count;
+                }
+                Some(RepoContributions {
+                    full_name: private_repos_label(private.len()),
+                    html_url: String::new(),
+                    stargazer_count: private.iter().map(|x| x.stargazer_count).sum(),
+                    is_private: true,
+                    counts,
+                    first_contribution_at: private.iter().map(|x| x.first_contribution_at).min()?,
+                    last_contribution_at: private.iter().map(|x| x.last_contribution_at).max()?,
+                })
+            },
+        )
+    }
+
+    /// Moves the private repositories into one row built by `anonymize`, after the public ones.
+    fn merge<'a, T: Clone>(
+        self,
+        repos: &'a [T],
+        is_private: fn(&T) -> bool,
+        anonymize: impl FnOnce(&[T]) -> Option<T>,
+    ) -> Cow<'a, [T]> {
+        if self == PrivateRepos::Show || !repos.iter().any(is_private) {
+            return Cow::Borrowed(repos);
+        }
+        let (private, mut public): (Vec<T>, Vec<T>) = repos.iter().cloned().partition(is_private);
+        public.extend(anonymize(&private));
+        Cow::Owned(public)
+    }
+}
+
+impl std::fmt::Display for PrivateRepos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for PrivateRepos {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "show" => Ok(PrivateRepos::Show),
+            "anonymize" | "hide" => Ok(PrivateRepos::Anonymize),
+            _ => Err(format!(
+                "unknown private repos mode '{s}', expected show or anonymize"
+            )),
+        }
+    }
+}
+
+fn private_repos_label(count: usize) -> String {
+    if count == 1 {
+        "1 private repository".to_string()
+    } else {
+        format!("{count} private repositories")
+    }
+}
+
+/// A column of the repository tables, which both renderers draw alike.
+///
+/// Each table shows the columns it has data for, see [`TableRow`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Column {
+    /// The position of the repository in the table.
+    Number,
+    Name,
+    Language,
+    Stars,
+    Forks,
+    Description,
+    Created,
+    /// When the repository was last pushed to.
+    LastUpdate,
+    /// The first contribution, which links to it in the contributed repos table.
+    First,
+    /// The latest contribution, which links to it in the contributed repos table.
+    Last,
+    /// Pull requests opened.
+    Prs,
+    /// Pull requests merged.
+    Merged,
+    /// Lines added and deleted by the pull requests.
+    LinesChanged,
+    Issues,
+    /// Pull requests by others that were reviewed.
+    Reviews,
+    /// Discussions where a comment was marked as the answer.
+    Answers,
+}
+
+/// Which side of its column a cell lines up with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Alignment {
+    Left,
+    Right,
+}
+
+/// How a column shares the width of an SVG card, see [`fit_columns`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum WidthHint {
+    /// As wide as its widest cell.
+    Fixed,
+    /// Truncates its cells rather than get narrower than `min`.
+    Text { min: i32 },
+}
+
+/// What a column shows in a row, which each renderer draws its own way.
+enum CellValue<'a> {
+    Text(Cow<'a, str>),
+    /// A link, struck through when the repository is archived.
+    Link {
+        text: Cow<'a, str>,
+        url: String,
+        archived: bool,
+    },
+    /// A language with its icon.
+    Language(&'a str),
+    /// A number, linking to `url` unless it's empty.
+    Count {
+        count: u32,
+        url: String,
+    },
+    LinesChanged {
+        additions: u32,
+        deletions: u32,
+    },
+}
+
+impl CellValue<'_> {
+    fn count(count: u32) -> Self {
+        CellValue::Count {
+            count,
+            url: String::new(),
+        }
+    }
+
+    fn date(date: DateTime<Utc>) -> Self {
+        CellValue::Text(Cow::Owned(date.format("%Y-%m-%d").to_string()))
+    }
+}
+
+impl Column {
+    pub const ALL: [Column; 16] = [
+        Column::Number,
+        Column::Name,
+        Column::Language,
+        Column::Stars,
+        Column::Forks,
+        Column::Description,
+        Column::Created,
+        Column::LastUpdate,
+        Column::First,
+        Column::Last,
+        Column::Prs,
+        Column::Merged,
+        Column::LinesChanged,
+        Column::Issues,
+        Column::Reviews,
+        Column::Answers,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Column::Number => "no",
+            Column::Name => "name",
+            Column::Language => "language",
+            Column::Stars => "stars",
+            Column::Forks => "forks",
+            Column::Description => "description",
+            Column::Created => "created",
+            Column::LastUpdate => "updated",
+            Column::First => "first",
+            Column::Last => "last",
+            Column::Prs => "prs",
+            Column::Merged => "merged",
+            Column::LinesChanged => "lines_changed",
+            Column::Issues => "issues",
+            Column::Reviews => "reviews",
+            Column::Answers => "answers",
+        }
+    }
+
+    #[must_use]
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Number => "No.",
+            Column::Name => "Name",
+            Column::Language => "Language",
+            Column::Stars => "Stars",
+            Column::Forks => "Forks",
+            Column::Description => "Description",
+            Column::Created => "Created",
+            Column::LastUpdate => "Last Update",
+            Column::First => "First",
+            Column::Last => "Last",
+            Column::Prs => "PRs",
+            Column::Merged => "Merged",
+            Column::LinesChanged => "Lines Changed",
+            Column::Issues => "Issues",
+            Column::Reviews => "Reviews",
+            Column::Answers => "Answers",
+        }
+    }
+
+    #[must_use]
+    pub fn alignment(self) -> Alignment {
+        match self {
+            Column::Stars | Column::Forks | Column::Merged => Alignment::Right,
+            _ if self.kind().is_some() => Alignment::Right,
+            _ => Alignment::Left,
+        }
+    }
+
+    fn width_hint(self) -> WidthHint {
+        match self {
+            Column::Name | Column::Description => WidthHint::Text { min: 120 },
+            Column::Language => WidthHint::Text { min: 0 },
+            _ => WidthHint::Fixed,
+        }
+    }
+
+    /// The kind of contribution the column counts, if any.
+    fn kind(self) -> Option<ContributionKind> {
+        match self {
+            Column::Prs => Some(ContributionKind::PullRequest),
+            Column::Issues => Some(ContributionKind::Issue),
+            Column::Reviews => Some(ContributionKind::Review),
+            Column::Answers => Some(ContributionKind::DiscussionAnswer),
+            _ => None,
+        }
+    }
+
+    /// The column counting contributions of `kind`.
+    fn of_kind(kind: ContributionKind) -> Column {
+        match kind {
+            ContributionKind::PullRequest => Column::Prs,
+            ContributionKind::Issue => Column::Issues,
+            ContributionKind::Review => Column::Reviews,
+            ContributionKind::DiscussionAnswer => Column::Answers,
+        }
+    }
+}
+
+impl std::fmt::Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|column| column.as_str() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|column| column.as_str()).collect();
+                format!("unknown column '{s}', expected one of {}", names.join(", "))
+            })
+    }
+}
+
+/// A row of one of the repository tables, which shows it in [`Column`]s.
+trait TableRow: Sized {
+    /// The columns the table has data for.
+    const COLUMNS: &'static [Column];
+    /// The columns of the table when none of the selected ones are in [`Self::COLUMNS`].
+    const DEFAULT_COLUMNS: &'static [Column];
+
+    /// What `column` shows for this, the `index`th row.
+    fn value(&self, column: Column, index: usize, author: &str) -> CellValue<'_>;
+
+    /// What `column` adds up to over `rows` in the total row, if anything.
+    fn total(rows: &[Self], column: Column) -> Option<CellValue<'static>>;
+}
+
+impl TableRow for Repository {
+    const COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Language,
+        Column::Stars,
+        Column::Forks,
+        Column::Description,
+        Column::Created,
+        Column::LastUpdate,
+    ];
+    const DEFAULT_COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Language,
+        Column::Stars,
+        Column::Forks,
+        Column::LastUpdate,
+    ];
+
+    fn value(&self, column: Column, index: usize, author: &str) -> CellValue<'_> {
+        match column {
+            Column::Number => CellValue::Text(Cow::Owned((index + 1).to_string())),
+            Column::Name => CellValue::Link {
+                text: Cow::Borrowed(self.display_name(author)),
+                url: self.html_url(),
+                archived: self.is_archived,
+            },
+            Column::Language => CellValue::Language(self.language()),
+            Column::Stars => CellValue::count(self.stargazer_count),
+            Column::Forks => CellValue::count(self.fork_count),
+            Column::Description => {
+                CellValue::Text(Cow::Borrowed(self.description.as_deref().unwrap_or("")))
+            }
+            Column::Created => CellValue::date(self.created_at),
+            Column::LastUpdate => self
+                .pushed_at
+                .map_or(CellValue::Text(Cow::Borrowed("N/A")), CellValue::date),
+            _ => CellValue::Text(Cow::Borrowed("")),
+        }
+    }
+
+    fn total(rows: &[Self], column: Column) -> Option<CellValue<'static>> {
+        match column {
+            Column::Stars => Some(CellValue::count(
+                rows.iter().map(|x| x.stargazer_count).sum(),
+            )),
+            Column::Forks => Some(CellValue::count(rows.iter().map(|x| x.fork_count).sum())),
+            _ => None,
+        }
+    }
+}
+
+impl TableRow for ContributedRepo {
+    const COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Language,
+        Column::Stars,
+        Column::Forks,
+        Column::Description,
+        Column::First,
+        Column::Last,
+        Column::Prs,
+        Column::Merged,
+        Column::LinesChanged,
+    ];
+    const DEFAULT_COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Stars,
+        Column::First,
+        Column::Last,
+        Column::Prs,
+    ];
+
+    fn value(&self, column: Column, index: usize, author: &str) -> CellValue<'_> {
+        let pr_link = |pr: &PullRequest| CellValue::Link {
+            text: Cow::Owned(pr.created_at.format("%Y-%m-%d").to_string()),
+            url: pr.url.clone(),
+            archived: false,
+        };
+        match column {
+            Column::Number => CellValue::Text(Cow::Owned((index + 1).to_string())),
+            Column::Name => CellValue::Link {
+                text: Cow::Borrowed(&self.full_name),
+                url: self.html_url.clone(),
+                archived: self.is_archived,
+            },
+            Column::Language => CellValue::Language(self.language()),
+            Column::Stars => CellValue::count(self.stargazer_count),
+            Column::Forks => CellValue::count(self.fork_count),
+            Column::Description => {
+                CellValue::Text(Cow::Borrowed(self.description.as_deref().unwrap_or("")))
+            }
+            Column::First => pr_link(&self.first_pr),
+            Column::Last => pr_link(&self.last_pr),
+            Column::Prs => CellValue::Count {
+                count: self.pr_count,
+                url: pulls_url(&self.html_url, "", author),
+            },
+            Column::Merged => CellValue::Count {
+                count: self.merged_count,
+                url: pulls_url(&self.html_url, "is%3Amerged+", author),
+            },
+            Column::LinesChanged => CellValue::LinesChanged {
+                additions: self.additions,
+                deletions: self.deletions,
+            },
+            _ => CellValue::Text(Cow::Borrowed("")),
+        }
+    }
+
+    fn total(rows: &[Self], column: Column) -> Option<CellValue<'static>> {
+        match column {
+            Column::Prs => Some(CellValue::count(rows.iter().map(|x| x.pr_count).sum())),
+            Column::Merged => Some(CellValue::count(rows.iter().map(|x| x.merged_count).sum())),
+            Column::LinesChanged => Some(CellValue::LinesChanged {
+                additions: rows.iter().map(|x| x.additions).sum(),
+                deletions: rows.iter().map(|x| x.deletions).sum(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TableRow for RepoContributions {
+    const COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Stars,
+        Column::First,
+        Column::Last,
+        Column::Prs,
+        Column::Issues,
+        Column::Reviews,
+        Column::Answers,
+    ];
+    const DEFAULT_COLUMNS: &'static [Column] = &[
+        Column::Number,
+        Column::Name,
+        Column::Stars,
+        Column::First,
+        Column::Last,
+    ];
+
+    fn value(&self, column: Column, index: usize, author: &str) -> CellValue<'_> {
+        match column {
+            Column::Number => CellValue::Text(Cow::Owned((index + 1).to_string())),
+            Column::Name => CellValue::Link {
+                text: Cow::Borrowed(&self.full_name),
+                url: self.html_url.clone(),
+                archived: false,
+            },
+            Column::Stars => CellValue::count(self.stargazer_count),
+            Column::First => CellValue::date(self.first_contribution_at),
+            Column::Last => CellValue::date(self.last_contribution_at),
+            _ => match column.kind() {
+                Some(kind) => CellValue::Count {
+                    count: self.count(kind),
+                    url: kind_search_url(kind, &self.html_url, author),
+                },
+                None => CellValue::Text(Cow::Borrowed("")),
+            },
+        }
+    }
+
+    fn total(rows: &[Self], column: Column) -> Option<CellValue<'static>> {
+        let kind = column.kind()?;
+        Some(CellValue::count(rows.iter().map(|x| x.count(kind)).sum()))
+    }
+}
+
+/// The `selected` columns that `R`'s table has, in order, or its default ones when there are none.
+fn table_columns<R: TableRow>(selected: &[Column]) -> Vec<Column> {
+    let columns: Vec<_> = selected
+        .iter()
+        .copied()
+        .filter(|column| R::COLUMNS.contains(column))
+        .collect();
+    if columns.is_empty() {
+        R::DEFAULT_COLUMNS.to_vec()
+    } else {
+        columns
+    }
+}
+
+/// The columns of the contributions table, followed by a count of each of `kinds` not selected.
+fn contributions_columns(selected: &[Column], kinds: &[ContributionKind]) -> Vec<Column> {
+    let selected: Vec<_> = selected
+        .iter()
+        .copied()
+        .filter(|column| column.kind().is_none_or(|kind| kinds.contains(&kind)))
+        .collect();
+    let mut columns = table_columns::<RepoContributions>(&selected);
+    for &kind in kinds {
+        let column = Column::of_kind(kind);
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    columns
+}
+
+/// Keeps the cell on one row of a Markdown table.
+fn markdown_cell(value: CellValue<'_>, alignment: Alignment) -> Cell {
+    let text = match value {
+        CellValue::Text(text) => text.replace('|', "\\|").replace(['\r', '\n'], " "),
+        CellValue::Link {
+            text,
+            url,
+            archived,
+        } => {
+            let archived = if archived { "~~" } else { "" };
+            format!("{archived}{}{archived}", markdown_link(text, &url))
+        }
+        CellValue::Language(language) => language.to_string(),
+        CellValue::Count { count, url } => markdown_link(count, &url),
+        CellValue::LinesChanged {
+            additions,
+            deletions,
+        } => format!("+{additions} / -{deletions}"),
+    };
+    let alignment = match alignment {
+        Alignment::Left => prettytable::format::Alignment::LEFT,
+        Alignment::Right => prettytable::format::Alignment::RIGHT,
+    };
+    Cell::new_align(&text, alignment)
 }
 
 #[derive(Default)]
-pub struct MarkdownRenderer {}
+pub struct MarkdownRenderer {
+    columns: Vec<Column>,
+    private_repos: PrivateRepos,
+}
 
 impl MarkdownRenderer {
     pub fn new() -> Self {
-        MarkdownRenderer {}
+        MarkdownRenderer::default()
     }
-}
 
-impl Render for MarkdownRenderer {
-    fn render_created_repos(&self, output: &mut String, repos: &[Repository], _author: &str) {
+    /// Shows `columns` in the tables, in order.
+    ///
+    /// Each table leaves out the columns it has no data for, and shows its
+    /// default ones when that leaves none, as it does when `columns` is empty.
+    #[must_use]
+    pub fn with_columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Chooses whether private repositories are listed by name.
+    #[must_use]
+    pub fn with_private_repos(mut self, private_repos: PrivateRepos) -> Self {
+        self.private_repos = private_repos;
+        self
+    }
+
+    /// Renders `rows` in `columns`, followed by a row with the totals.
+    fn render_table<R: TableRow>(
+        &self,
+        output: &mut String,
+        rows: &[R],
+        columns: &[Column],
+        author: &str,
+    ) {
         let mut table = Table::new();
         table.set_format(*MARKDOWN_TABLE);
-        table.set_titles(row![
-            "No.",
-            "Name",
-            "Language",
-            "Stars",
-            "Forks",
-            "Last Update"
-        ]);
-
-        for (id, repo) in repos.iter().enumerate() {
-            let archived = if repo.is_archived { "~~" } else { "" };
-            table.add_row(row![
-                id + 1,
-                format!("{archived}[{}]({}){archived}", repo.name(), repo.html_url()),
-                repo.language(),
-                repo.stargazer_count,
-                repo.fork_count,
-                repo.pushed_at
-                    .map_or("N/A".to_string(), |dt| dt.format("%Y-%m-%d").to_string()),
-            ]);
+        table.set_titles(Row::new(
+            columns
+                .iter()
+                .map(|column| {
+                    markdown_cell(
+                        CellValue::Text(Cow::Borrowed(column.header())),
+                        column.alignment(),
+                    )
+                })
+                .collect(),
+        ));
+
+        for (id, row) in rows.iter().enumerate() {
+            table.add_row(Row::new(
+                columns
+                    .iter()
+                    .map(|&column| markdown_cell(row.value(column, id, author), column.alignment()))
+                    .collect(),
+            ));
         }
-        table.add_row(row![
-            "Total",
-            "",
-            "",
-            repos.iter().map(|x| x.stargazer_count).sum::<u32>(),
-            repos.iter().map(|x| x.fork_count).sum::<u32>(),
-            "",
-        ]);
+        // The first column says what the row is, unless it has a total of its own.
+        table.add_row(Row::new(
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, &column)| match R::total(rows, column) {
+                    Some(total) => markdown_cell(total, column.alignment()),
+                    None if i == 0 => Cell::new("Total"),
+                    None => Cell::new(""),
+                })
+                .collect(),
+        ));
 
         output.push_str(table.to_string().as_str());
     }
+}
+
+impl Render for MarkdownRenderer {
+    fn render_created_repos(&self, output: &mut String, repos: &[Repository], author: &str) {
+        let repos = self.private_repos.created(repos);
+        let columns = table_columns::<Repository>(&self.columns);
+        self.render_table(output, &repos, &columns, author);
+    }
 
     fn render_contributed_repos(
         &self,
@@ -95,59 +860,76 @@ impl Render for MarkdownRenderer {
         repos: &[ContributedRepo],
         author: &str,
     ) {
+        let repos = self.private_repos.contributed(repos);
+        let columns = table_columns::<ContributedRepo>(&self.columns);
+        self.render_table(output, &repos, &columns, author);
+    }
+
+    fn render_contributions(
+        &self,
+        output: &mut String,
+        repos: &[RepoContributions],
+        kinds: &[ContributionKind],
+        author: &str,
+    ) {
+        let repos = self.private_repos.contributions(repos);
+        let columns = contributions_columns(&self.columns, kinds);
+        self.render_table(output, &repos, &columns, author);
+    }
+
+    fn render_summary(&self, output: &mut String, stats: &ContributionStats, _author: &str) {
         let mut table = Table::new();
         table.set_format(*MARKDOWN_TABLE);
-        table.set_titles(row![
-            "No.", "Name", "Stars", "First PR", "Last PR", "PR Count"
-        ]);
+        table.set_titles(row!["Summary", ""]);
+        for (label, value) in summary_rows(stats) {
+            table.add_row(row![label, value]);
+        }
+        output.push_str(table.to_string().as_str());
 
-        for (id, repo) in repos.iter().enumerate() {
+        let mut table = Table::new();
+        table.set_format(*MARKDOWN_TABLE);
+        table.set_titles(row!["Year", "PRs", "Merged", "Repos"]);
+        for (year, stats) in stats.years.iter().rev() {
             table.add_row(row![
-                id + 1,
-                format!(
-                    "[{}](https://github.com/{})",
-                    repo.full_name, repo.full_name
-                ),
-                format!("{}", repo.stargazer_count),
-                format!(
-                    "[{}]({})",
-                    repo.first_pr.created_at.format("%Y-%m-%d"),
-                    repo.first_pr.url.as_str()
-                ),
-                format!(
-                    "[{}]({})",
-                    repo.last_pr.created_at.format("%Y-%m-%d"),
-                    repo.last_pr.url.as_str()
-                ),
-                format!(
-                    "[{}](https://github.com/{}/pulls?q=is%3Apr+author%3A{})",
-                    repo.pr_count, repo.full_name, author
-                )
+                year,
+                stats.pr_count,
+                stats.merged_count,
+                stats.repo_count
             ]);
         }
-        table.add_row(row![
-            "Total",
-            "",
-            "",
-            "",
-            "",
-            repos.iter().map(|x| x.pr_count).sum::<u32>(),
-        ]);
+        output.push('\n');
         output.push_str(table.to_string().as_str());
     }
 }
 
+/// The rules that paint the elements of each [`ThemeColor`] class in `theme`'s colours.
+fn theme_css(theme: &Theme) -> String {
+    let mut css = format!("svg {{ background-color: {} }} ", theme.background);
+    for color in ThemeColor::ALL {
+        css.push_str(&format!(".{color} {{ fill: {} }} ", theme.get(color)));
+    }
+    css
+}
+
+/// Fills SVG elements with theme colours, see [`SvgRenderer::fill`].
+trait Fill: Node + Sized {
+    fn fill(mut self, renderer: &SvgRenderer, color: ThemeColor) -> Self {
+        renderer.fill(&mut self, color);
+        self
+    }
+}
+
+impl<T: Node> Fill for T {}
+
 pub struct SvgRenderer {
+    web_url: String,
     font_family: String,
-    header_bg: String,
-    row_bg_even: String,
-    row_bg_odd: String,
-    text_color: String,
-    link_color: String,
-    star_color: String,
-    fork_color: String,
-    pr_color: String,
-    total_row_bg: String,
+    theme: Theme,
+    dark_theme: Option<Theme>,
+    width: i32,
+    compact: bool,
+    columns: Vec<Column>,
+    private_repos: PrivateRepos,
 }
 
 impl Default for SvgRenderer {
@@ -157,18 +939,106 @@ impl Default for SvgRenderer {
 }
 
 impl SvgRenderer {
+    /// The width of the cards unless [`SvgRenderer::with_width`] changes it.
+    pub const DEFAULT_WIDTH: u32 = 780;
+    /// The narrowest card [`SvgRenderer::with_width`] draws.
+    pub const MIN_WIDTH: u32 = 400;
+    /// The widest card [`SvgRenderer::with_width`] draws.
+    pub const MAX_WIDTH: u32 = 2000;
+
     pub fn new() -> Self {
         SvgRenderer {
+            web_url: DEFAULT_WEB_URL.to_string(),
             font_family: "Arial, sans-serif".to_string(),
-            header_bg: "#E8EEF6".to_string(),    // Soft cool header
-            row_bg_even: "#ffffff".to_string(),  // White
-            row_bg_odd: "#F1F5F9".to_string(),   // Light cool gray
-            text_color: "#334155".to_string(),   // Slate gray
-            link_color: "#2563EB".to_string(),   // Bright blue for links
-            star_color: "#EAB308".to_string(),   // Yellow for stars
-            fork_color: "#10B981".to_string(),   // Emerald for forks
-            pr_color: "#2DA44E".to_string(),     // GitHub PR green
-            total_row_bg: "#E2E8F0".to_string(), // Cool gray for total
+            theme: Theme::default(),
+            dark_theme: None,
+            width: Self::DEFAULT_WIDTH as i32,
+            compact: false,
+            columns: Vec::new(),
+            private_repos: PrivateRepos::default(),
+        }
+    }
+
+    /// Draws the cards in the colours of `theme`.
+    #[must_use]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Switches to `dark_theme` for viewers who prefer a dark colour scheme.
+    ///
+    /// The colours then move from the elements into a stylesheet, whose
+    /// `prefers-color-scheme: dark` media query swaps them.
+    #[must_use]
+    pub fn with_dark_theme(mut self, dark_theme: Option<Theme>) -> Self {
+        self.dark_theme = dark_theme;
+        self
+    }
+
+    /// Draws the cards `width` pixels wide, between [`Self::MIN_WIDTH`] and [`Self::MAX_WIDTH`].
+    ///
+    /// The columns of the tables share the width in proportion to their content.
+    #[must_use]
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = width.clamp(Self::MIN_WIDTH, Self::MAX_WIDTH) as i32;
+        self
+    }
+
+    /// Draws lower rows, for cards embedded in a sidebar.
+    #[must_use]
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Shows `columns` in the tables, in order.
+    ///
+    /// Each table leaves out the columns it has no data for, and shows its
+    /// default ones when that leaves none, as it does when `columns` is empty.
+    #[must_use]
+    pub fn with_columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Points profile links at `web_url`, e.g. a GitHub Enterprise Server host.
+    #[must_use]
+    pub fn with_web_url(mut self, web_url: impl Into<String>) -> Self {
+        self.web_url = web_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Chooses whether private repositories are listed by name.
+    #[must_use]
+    pub fn with_private_repos(mut self, private_repos: PrivateRepos) -> Self {
+        self.private_repos = private_repos;
+        self
+    }
+
+    fn create_document(&self, width: i32, height: i32) -> Document {
+        let document = Document::new()
+            .set("xmlns", "http://www.w3.org/2000/svg")
+            .set("preserveAspectRatio", "xMidYMin meet")
+            .set("viewBox", format!("0 0 {width} {height}"));
+        match &self.dark_theme {
+            Some(dark_theme) => document.add(Style::new(format!(
+                "{}@media (prefers-color-scheme: dark) {{ {}}}",
+                theme_css(&self.theme),
+                theme_css(dark_theme)
+            ))),
+            None => document.set(
+                "style",
+                format!("background-color: {}", self.theme.background),
+            ),
+        }
+    }
+
+    /// Fills `node` with `color`, through its CSS class when the cards follow the colour scheme.
+    fn fill(&self, node: &mut impl Node, color: ThemeColor) {
+        match self.dark_theme {
+            Some(_) => node.assign("class", color.as_str()),
+            None => node.assign("fill", self.theme.get(color)),
         }
     }
 
@@ -176,7 +1046,7 @@ impl SvgRenderer {
         Text::new(content)
             .set("x", x)
             .set("y", y)
-            .set("fill", self.text_color.as_str())
+            .fill(self, ThemeColor::Text)
             .set("font-family", self.font_family.as_str())
             .set("font-size", 12)
             .set("text-anchor", "end")
@@ -192,24 +1062,24 @@ impl SvgRenderer {
             .set("dy", STATS_HEADER_TEXT_DY)
             .add(
                 TSpan::new(title)
-                    .set("fill", self.text_color.as_str())
+                    .fill(self, ThemeColor::Text)
                     .set("font-weight", "bold"),
             )
             .add(
                 TSpan::new("by")
                     .set("dx", STATS_HEADER_TEXT_GAP)
-                    .set("fill", self.text_color.as_str())
+                    .fill(self, ThemeColor::Text)
                     .set("font-size", 14)
                     .set("opacity", 0.72),
             )
             .add(
                 Anchor::new()
-                    .set("href", format!("https://github.com/{username}"))
+                    .set("href", format!("{}/{username}", self.web_url))
                     .set("target", "_blank")
                     .add(
                         TSpan::new(username)
                             .set("dx", STATS_HEADER_TEXT_GAP)
-                            .set("fill", self.link_color.as_str())
+                            .fill(self, ThemeColor::Link)
                             .set("font-size", 14)
                             .set("opacity", 0.85),
                     ),
@@ -230,20 +1100,31 @@ impl SvgRenderer {
         let icon_y = -STATS_HEADER_ICON_SIZE / 2;
         let title_x = 38;
         let timestamp_x = width - 10;
+        let timestamp = format!("Updated {current_date}");
 
-        Group::new()
+        let header = Group::new()
             .set("transform", format!("translate(0 {content_center_y})"))
             .add(
                 Path::new()
                     .set("d", icon_path)
-                    .set("fill", self.text_color.as_str())
+                    .fill(self, ThemeColor::Text)
                     .set(
                         "transform",
                         format!("translate(10 {icon_y}) scale({icon_scale})"),
                     ),
             )
-            .add(self.create_stats_header_subject(title_x, 0, title, username))
-            .add(self.create_timestamp(timestamp_x, 0, &format!("Updated {current_date}")))
+            .add(self.create_stats_header_subject(title_x, 0, title, username));
+
+        // Narrow cards drop the timestamp rather than overlap the subject with it.
+        let subject_end = f64::from(title_x + 2 * STATS_HEADER_TEXT_GAP)
+            + font::text_width(title, 16.0, true)
+            + font::text_width("by", 14.0, false)
+            + font::text_width(username, 14.0, false);
+        let timestamp_start = f64::from(timestamp_x) - font::text_width(&timestamp, 12.0, false);
+        if subject_end + 10.0 > timestamp_start {
+            return header;
+        }
+        header.add(self.create_timestamp(timestamp_x, 0, &timestamp))
     }
 
     fn create_stats_footer(&self, width: i32, y: i32) -> Text {
@@ -256,7 +1137,7 @@ impl SvgRenderer {
             .set("dominant-baseline", "middle")
             .add(
                 TSpan::new("Generated by ")
-                    .set("fill", self.text_color.as_str())
+                    .fill(self, ThemeColor::Text)
                     .set("opacity", 0.55),
             )
             .add(
@@ -265,7 +1146,7 @@ impl SvgRenderer {
                     .set("target", "_blank")
                     .add(
                         TSpan::new("github-contrib-stats")
-                            .set("fill", self.link_color.as_str())
+                            .fill(self, ThemeColor::Link)
                             .set("opacity", 0.85),
                     ),
             )
@@ -393,6 +1274,11 @@ impl SvgRenderer {
         Some((color, path, scale_factor, view_box))
     }
 
+    fn language_color(&self, language: &str) -> &'static str {
+        self.get_language_icon(language)
+            .map_or(OTHER_LANGUAGE_COLOR, |(color, ..)| color)
+    }
+
     fn create_language_icon(&self, x: i32, y: i32, language: &str) -> Option<Group> {
         self.get_language_icon(language)
             .map(|(color, _, scale_factor, _)| {
@@ -434,17 +1320,17 @@ impl SvgRenderer {
         x: i32,
         y: i32,
         number: u32,
-        color: &str,
+        color: ThemeColor,
         is_star: bool,
         bold: bool,
     ) -> Group {
         let num_str = number.to_string();
-        let num_width = (num_str.len() as i32 * 8) + 8;
+        let num_width = self.measure(&num_str, bold) + 8;
 
         let mut text = Text::new(num_str)
             .set("x", x)
             .set("y", y)
-            .set("fill", color)
+            .fill(self, color)
             .set("font-family", self.font_family.as_str())
             .set("font-size", 14)
             .set("dominant-baseline", "middle");
@@ -462,7 +1348,7 @@ impl SvgRenderer {
                     .set("y", y - 10)
                     .set("width", num_width)
                     .set("height", 20)
-                    .set("fill", color)
+                    .fill(self, color)
                     .set("opacity", 0.2)
                     .set("rx", 10),
             );
@@ -473,7 +1359,7 @@ impl SvgRenderer {
                     .set("y", y - 10)
                     .set("width", num_width)
                     .set("height", 20)
-                    .set("fill", color)
+                    .fill(self, color)
                     .set("opacity", 0.15)
                     .set("rx", 10),
             );
@@ -484,7 +1370,7 @@ impl SvgRenderer {
                     .set("y", y - 10)
                     .set("width", num_width)
                     .set("height", 20)
-                    .set("fill", color)
+                    .fill(self, color)
                     .set("opacity", 0.08)
                     .set("rx", 10),
             );
@@ -511,30 +1397,68 @@ impl SvgRenderer {
         group
     }
 
-    fn create_text(&self, x: i32, y: i32, content: &str, color: &str) -> Text {
+    fn create_lines_changed(&self, x: i32, y: i32, additions: u32, deletions: u32) -> Text {
+        Text::new("")
+            .set("x", x)
+            .set("y", y)
+            .set("font-family", self.font_family.as_str())
+            .set("font-size", 14)
+            .set("dominant-baseline", "middle")
+            .add(TSpan::new(format!("+{additions}")).fill(self, ThemeColor::Addition))
+            .add(TSpan::new(format!(" -{deletions}")).fill(self, ThemeColor::Deletion))
+    }
+
+    /// The colour of the numbers in `column`.
+    fn count_color(&self, column: Column) -> ThemeColor {
+        match column {
+            Column::Stars => ThemeColor::Star,
+            Column::Forks => ThemeColor::Fork,
+            Column::Merged => ThemeColor::Merged,
+            _ => match column.kind() {
+                Some(ContributionKind::PullRequest) => ThemeColor::Pr,
+                Some(ContributionKind::Issue) => ThemeColor::Issue,
+                Some(ContributionKind::Review) => ThemeColor::Review,
+                Some(ContributionKind::DiscussionAnswer) => ThemeColor::Answer,
+                None => ThemeColor::Text,
+            },
+        }
+    }
+
+    fn create_text(&self, x: i32, y: i32, content: &str, color: ThemeColor) -> Text {
         Text::new(content)
             .set("x", x)
             .set("y", y)
-            .set("fill", color)
+            .fill(self, color)
             .set("font-family", self.font_family.as_str())
             .set("font-size", 14)
             .set("dominant-baseline", "middle") // Vertical alignment
     }
 
+    /// A link to `url`, or a plain group when the row has no URL.
+    fn create_anchor(&self, url: &str) -> Anchor {
+        if url.is_empty() {
+            return Anchor::new();
+        }
+        Anchor::new().set("href", url).set("target", "_blank")
+    }
+
     fn create_link(&self, x: i32, y: i32, text: &str, url: &str) -> Anchor {
-        Anchor::new()
-            .set("href", url)
-            .set("target", "_blank")
-            .add(self.create_text(x, y, text, &self.link_color))
+        let color = if url.is_empty() {
+            ThemeColor::Text
+        } else {
+            ThemeColor::Link
+        };
+        self.create_anchor(url)
+            .add(self.create_text(x, y, text, color))
     }
 
-    fn create_rect(&self, x: i32, y: i32, width: i32, height: i32, fill: &str) -> Rectangle {
+    fn create_rect(&self, x: i32, y: i32, width: i32, height: i32, fill: ThemeColor) -> Rectangle {
         Rectangle::new()
             .set("x", x)
             .set("y", y)
             .set("width", width)
             .set("height", height)
-            .set("fill", fill)
+            .fill(self, fill)
             .set("rx", 4) // Rounded corners
     }
 
@@ -542,57 +1466,228 @@ impl SvgRenderer {
         Text::new(content)
             .set("x", x)
             .set("y", y)
-            .set("fill", self.text_color.as_str())
+            .fill(self, ThemeColor::Text)
             .set("font-family", self.font_family.as_str())
             .set("font-size", 14)
             .set("font-weight", "bold")
             .set("dominant-baseline", "middle")
     }
 
+    /// Cuts `text` short to fit a `max_width` column, leaving [`COLUMN_GAP`] to the next one.
     fn truncate_text(&self, text: &str, max_width: i32) -> String {
-        if text.len() * 8 > (max_width - 20) as usize {
-            // Approximate character width of 8px
-            let max_chars = (max_width - 40) as usize / 8; // Leave room for "..."
-            format!("{}...", &text[..max_chars])
+        font::truncate(text, f64::from(max_width - COLUMN_GAP), 14.0, false).into_owned()
+    }
+
+    fn row_height(&self) -> i32 {
+        if self.compact {
+            COMPACT_ROW_HEIGHT
         } else {
-            text.to_string()
+            ROW_HEIGHT
         }
     }
-}
 
-impl Render for SvgRenderer {
-    fn render_created_repos(&self, output: &mut String, repos: &[Repository], author: &str) {
-        let col_widths = [
-            50,  // No.
-            250, // Name
-            140, // Language
-            120, // Stars (including space for fire emoji)
-            100, // Forks
-            120, // Last Update
-        ];
-        let row_height = 40;
-        let stats_header_height = STATS_HEADER_HEIGHT;
-        let stats_footer_height = STATS_FOOTER_HEIGHT;
-        let total_width = SVG_WIDTH;
-        let total_height =
-            stats_header_height + (repos.len() as i32 + 2) * row_height + stats_footer_height;
+    /// The width of `text` in the font of the tables.
+    fn measure(&self, text: &str, bold: bool) -> i32 {
+        font::text_width(text, 14.0, bold).ceil() as i32
+    }
 
-        let mut document = Document::new()
-            .set("style", "background-color: white")
-            .set("xmlns", "http://www.w3.org/2000/svg")
-            .set("preserveAspectRatio", "xMidYMin meet")
-            .set("viewBox", format!("0 0 {total_width} {total_height}"));
+    /// How far right of its `x` a number drawn by [`Self::create_number_with_effect`] reaches.
+    fn number_width(&self, number: u32, is_star: bool, bold: bool) -> i32 {
+        let num_width = self.measure(&number.to_string(), bold) + 8;
+        if is_star && number >= 10000 {
+            // The fire emoji, a full em wide
+            num_width + 2 + 14
+        } else {
+            num_width - 4
+        }
+    }
+
+    /// How far right of the start of its column `value` reaches.
+    fn cell_width(&self, value: &CellValue<'_>, column: Column, bold: bool) -> i32 {
+        match value {
+            CellValue::Text(text) => self.measure(text, false),
+            CellValue::Link { text, .. } => self.measure(text, false),
+            CellValue::Language(language) => 25 + self.measure(language, false),
+            CellValue::Count { count, .. } => {
+                self.number_width(*count, column == Column::Stars, bold)
+            }
+            CellValue::LinesChanged {
+                additions,
+                deletions,
+            } => self.measure(&format!("+{additions} -{deletions}"), bold),
+        }
+    }
+
+    /// Draws `value` centred on `y` in the `width` wide cell of `column` that starts at `x`.
+    fn create_cell(
+        &self,
+        value: &CellValue<'_>,
+        column: Column,
+        x: i32,
+        width: i32,
+        y: i32,
+        bold: bool,
+    ) -> Box<dyn Node> {
+        let right = x + width - COLUMN_GAP;
+        match value {
+            CellValue::Text(text) => {
+                let text = self.truncate_text(text, width);
+                match column.alignment() {
+                    Alignment::Left => Box::new(self.create_text(x, y, &text, ThemeColor::Text)),
+                    Alignment::Right => Box::new(
+                        self.create_text(right, y, &text, ThemeColor::Text)
+                            .set("text-anchor", "end"),
+                    ),
+                }
+            }
+            CellValue::Link {
+                text,
+                url,
+                archived,
+            } => {
+                let mut link = self.create_link(x, y, &self.truncate_text(text, width), url);
+                if *archived {
+                    link = link.set("opacity", 0.6);
+                }
+                Box::new(link)
+            }
+            CellValue::Language(language) => {
+                let mut group = Group::new();
+                if let Some(icon) = self.create_language_icon(x, y, language) {
+                    group = group.add(icon);
+                }
+                let language = self.truncate_text(language, width - 25); // subtract icon space
+                Box::new(group.add(self.create_text(x + 25, y, &language, ThemeColor::Text)))
+            }
+            CellValue::Count { count, url } => {
+                // A fire after a right-aligned count takes up the gap to the next column.
+                let x = match column.alignment() {
+                    Alignment::Left => x,
+                    Alignment::Right => right - self.number_width(*count, false, bold),
+                };
+                let number = self.create_number_with_effect(
+                    x,
+                    y,
+                    *count,
+                    self.count_color(column),
+                    column == Column::Stars,
+                    bold,
+                );
+                if url.is_empty() {
+                    Box::new(number)
+                } else {
+                    Box::new(self.create_anchor(url).add(number))
+                }
+            }
+            CellValue::LinesChanged {
+                additions,
+                deletions,
+            } => {
+                let text = self.create_lines_changed(x, y, *additions, *deletions);
+                if bold {
+                    Box::new(text.set("font-weight", "bold"))
+                } else {
+                    Box::new(text)
+                }
+            }
+        }
+    }
 
-        let languages = repos.iter().map(|x| x.language()).collect::<Vec<_>>();
-        // Add definitions with all language icons
-        document = document.add(self.create_language_defs(&languages));
+    /// The natural width of a column, fitting its bold `header` and the `cells` widths.
+    fn column_width(&self, header: &str, cells: impl IntoIterator<Item = i32>) -> i32 {
+        cells.into_iter().fold(self.measure(header, true), i32::max) + COLUMN_GAP
+    }
+
+    /// A column of text that may be truncated to the wider of its header and `min`.
+    fn text_column(
+        &self,
+        header: &str,
+        min: i32,
+        cells: impl IntoIterator<Item = i32>,
+    ) -> ColumnSize {
+        ColumnSize::text(
+            self.column_width(header, cells),
+            min.max(self.measure(header, true) + COLUMN_GAP),
+        )
+    }
+
+    /// A column of numbers, dates and the like that is never truncated.
+    fn fixed_column(&self, header: &str, cells: impl IntoIterator<Item = i32>) -> ColumnSize {
+        ColumnSize::fixed(self.column_width(header, cells))
+    }
+
+    /// Draws a card with `rows` in `columns`, followed by a row with the totals.
+    fn render_table<R: TableRow>(
+        &self,
+        output: &mut String,
+        rows: &[R],
+        columns: &[Column],
+        title: &str,
+        icon_path: &str,
+        author: &str,
+    ) {
+        let row_height = self.row_height();
+        let stats_header_height = STATS_HEADER_HEIGHT;
+        let stats_footer_height = STATS_FOOTER_HEIGHT;
+        let total_width = self.width;
+        let cells: Vec<Vec<_>> = rows
+            .iter()
+            .enumerate()
+            .map(|(id, row)| {
+                columns
+                    .iter()
+                    .map(|&column| row.value(column, id, author))
+                    .collect()
+            })
+            .collect();
+        let totals: Vec<_> = columns
+            .iter()
+            .map(|&column| R::total(rows, column))
+            .collect();
+
+        let sizes: Vec<_> = columns
+            .iter()
+            .enumerate()
+            .map(|(col, &column)| {
+                let widths = cells
+                    .iter()
+                    .map(|row| self.cell_width(&row[col], column, false))
+                    .chain(
+                        totals[col]
+                            .as_ref()
+                            .map(|total| self.cell_width(total, column, true)),
+                    );
+                match column.width_hint() {
+                    WidthHint::Fixed => self.fixed_column(column.header(), widths),
+                    WidthHint::Text { min } => self.text_column(column.header(), min, widths),
+                }
+            })
+            .collect();
+        let col_widths = fit_columns(&sizes, total_width - 10);
+        let x_of = |col: usize| 10 + col_widths[..col].iter().sum::<i32>();
+        let total_height =
+            stats_header_height + (rows.len() as i32 + 2) * row_height + stats_footer_height;
+
+        let mut document = self.create_document(total_width, total_height);
+
+        if columns.contains(&Column::Language) {
+            let languages: Vec<_> = cells
+                .iter()
+                .flatten()
+                .filter_map(|value| match value {
+                    CellValue::Language(language) => Some(*language),
+                    _ => None,
+                })
+                .collect();
+            document = document.add(self.create_language_defs(&languages));
+        }
 
         document = document.add(self.create_stats_header(
             total_width,
             stats_header_height,
-            "Repositories created",
+            title,
             author,
-            REPO_ICON_PATH,
+            icon_path,
         ));
 
         // Header row
@@ -601,138 +1696,90 @@ impl Render for SvgRenderer {
             stats_header_height,
             total_width,
             row_height,
-            &self.header_bg,
+            ThemeColor::Header,
         ));
 
         // Header texts
-        let headers = ["No.", "Name", "Language", "Stars", "Forks", "Last Update"];
-        let mut x = 10;
-        for (i, header) in headers.iter().enumerate() {
-            document = document.add(self.create_header_text(
-                x,
-                stats_header_height + row_height / 2,
-                header,
-            ));
-            x += col_widths[i];
+        for (col, column) in columns.iter().enumerate() {
+            let y = stats_header_height + row_height / 2;
+            document = document.add(match column.alignment() {
+                Alignment::Left => self.create_header_text(x_of(col), y, column.header()),
+                Alignment::Right => self
+                    .create_header_text(x_of(col + 1) - COLUMN_GAP, y, column.header())
+                    .set("text-anchor", "end"),
+            });
         }
 
         // Data rows
         let mut y = stats_header_height + row_height;
-        for (id, repo) in repos.iter().enumerate() {
+        for (id, row) in cells.iter().enumerate() {
             let bg_color = if id % 2 == 0 {
-                &self.row_bg_even
+                ThemeColor::Row
             } else {
-                &self.row_bg_odd
+                ThemeColor::AltRow
             };
 
             // Row background
             document = document.add(self.create_rect(0, y, total_width, row_height, bg_color));
 
-            let mut x = 10;
-            // No.
-            document = document.add(self.create_text(
-                x,
-                y + row_height / 2,
-                &(id + 1).to_string(),
-                &self.text_color,
-            ));
-
-            // Name with link
-            x += col_widths[0];
-            let truncated_name = self.truncate_text(repo.name(), col_widths[1]);
-            document = document.add(self.create_link(
-                x,
-                y + row_height / 2,
-                &truncated_name,
-                &repo.html_url(),
-            ));
-
-            // Language
-            x += col_widths[1];
-            let text_x = x + 25;
-            if let Some(lang_icon) =
-                self.create_language_icon(x, y + row_height / 2, repo.language())
-            {
-                document = document.add(lang_icon);
+            for (col, (value, &column)) in row.iter().zip(columns).enumerate() {
+                document = document.add(self.create_cell(
+                    value,
+                    column,
+                    x_of(col),
+                    col_widths[col],
+                    y + row_height / 2,
+                    false,
+                ));
             }
-            let truncated_lang = self.truncate_text(repo.language(), col_widths[2] - 30); // subtract icon space
-            document = document.add(self.create_text(
-                text_x,
-                y + row_height / 2,
-                &truncated_lang,
-                &self.text_color,
-            ));
-
-            // Stars
-            x += col_widths[2];
-            document = document.add(self.create_number_with_effect(
-                x,
-                y + row_height / 2,
-                repo.stargazer_count,
-                &self.star_color,
-                true,
-                false,
-            ));
-
-            // Forks
-            x += col_widths[3];
-            document = document.add(self.create_number_with_effect(
-                x,
-                y + row_height / 2,
-                repo.fork_count,
-                &self.fork_color,
-                false,
-                false,
-            ));
-
-            // Last Update
-            x += col_widths[4];
-            let date = repo
-                .pushed_at
-                .map_or("N/A".to_string(), |dt| dt.format("%Y-%m-%d").to_string());
-            document =
-                document.add(self.create_text(x, y + row_height / 2, &date, &self.text_color));
 
             y += row_height;
         }
 
         // Total row
         document =
-            document.add(self.create_rect(0, y, total_width, row_height, &self.total_row_bg));
-
-        document = document.add(
-            self.create_text(10, y + row_height / 2, "Total", &self.text_color)
-                .set("font-weight", "bold"),
-        );
+            document.add(self.create_rect(0, y, total_width, row_height, ThemeColor::TotalRow));
 
-        let total_stars: u32 = repos.iter().map(|x| x.stargazer_count).sum();
-        let total_forks: u32 = repos.iter().map(|x| x.fork_count).sum();
-
-        let x_stars = 10 + col_widths[0] + col_widths[1] + col_widths[2];
-        document = document.add(self.create_number_with_effect(
-            x_stars,
-            y + row_height / 2,
-            total_stars,
-            &self.star_color,
-            true,
-            true,
-        ));
+        // The first column says what the row is, unless it has a total of its own.
+        if totals.first().is_some_and(Option::is_none) {
+            document = document.add(
+                self.create_text(10, y + row_height / 2, "Total", ThemeColor::Text)
+                    .set("font-weight", "bold"),
+            );
+        }
 
-        let x_forks = x_stars + col_widths[3];
-        document = document.add(self.create_number_with_effect(
-            x_forks,
-            y + row_height / 2,
-            total_forks,
-            &self.fork_color,
-            false,
-            true,
-        ));
+        for (col, (total, &column)) in totals.iter().zip(columns).enumerate() {
+            if let Some(total) = total {
+                document = document.add(self.create_cell(
+                    total,
+                    column,
+                    x_of(col),
+                    col_widths[col],
+                    y + row_height / 2,
+                    true,
+                ));
+            }
+        }
 
         document = document
             .add(self.create_stats_footer(total_width, y + row_height + stats_footer_height / 2));
 
         output.push_str(&document.to_string());
     }
+}
+
+impl Render for SvgRenderer {
+    fn render_created_repos(&self, output: &mut String, repos: &[Repository], author: &str) {
+        let repos = self.private_repos.created(repos);
+        self.render_table(
+            output,
+            &repos,
+            &table_columns::<Repository>(&self.columns),
+            "Repositories created",
+            REPO_ICON_PATH,
+            author,
+        );
+    }
 
     fn render_contributed_repos(
         &self,
@@ -740,161 +1787,440 @@ impl Render for SvgRenderer {
         repos: &[ContributedRepo],
         author: &str,
     ) {
-        let col_widths = [
-            50,  // No.
-            270, // Name
-            120, // Stars
-            120, // First PR
-            120, // Last PR
-            100, // PR Count
-        ];
-        let row_height = 40;
+        let repos = self.private_repos.contributed(repos);
+        self.render_table(
+            output,
+            &repos,
+            &table_columns::<ContributedRepo>(&self.columns),
+            "Repositories contributed to",
+            PULL_REQUEST_ICON_PATH,
+            author,
+        );
+    }
+
+    fn render_contributions(
+        &self,
+        output: &mut String,
+        repos: &[RepoContributions],
+        kinds: &[ContributionKind],
+        author: &str,
+    ) {
+        let repos = self.private_repos.contributions(repos);
+        self.render_table(
+            output,
+            &repos,
+            &contributions_columns(&self.columns, kinds),
+            kind_title(kinds),
+            PULL_REQUEST_ICON_PATH,
+            author,
+        );
+    }
+    fn render_summary(&self, output: &mut String, stats: &ContributionStats, author: &str) {
+        let row_height = self.row_height();
         let stats_header_height = STATS_HEADER_HEIGHT;
         let stats_footer_height = STATS_FOOTER_HEIGHT;
-        let total_width = SVG_WIDTH;
-        let total_height =
-            stats_header_height + (repos.len() as i32 + 2) * row_height + stats_footer_height;
+        let total_width = self.width;
+        let rows = summary_rows(stats);
+        let label_widths = fit_columns(
+            &[
+                ColumnSize::fixed(
+                    rows.iter()
+                        .map(|(label, _)| self.measure(label, true))
+                        .max()
+                        .unwrap_or(0)
+                        + COLUMN_GAP,
+                ),
+                self.text_column(
+                    "",
+                    120,
+                    rows.iter().map(|(_, value)| self.measure(value, false)),
+                ),
+            ],
+            total_width - 10,
+        );
+        let value_x = 10 + label_widths[0];
+        let year_headers = ["Year", "PRs", "Merged", "Repos"];
+        let year_widths = fit_columns(
+            &[
+                self.fixed_column(year_headers[0], [self.measure("0000", false)]),
+                self.fixed_column(
+                    year_headers[1],
+                    stats
+                        .years
+                        .values()
+                        .map(|year| self.number_width(year.pr_count, false, false)),
+                ),
+                self.fixed_column(
+                    year_headers[2],
+                    stats
+                        .years
+                        .values()
+                        .map(|year| self.number_width(year.merged_count, false, false)),
+                ),
+                self.fixed_column(
+                    year_headers[3],
+                    stats
+                        .years
+                        .values()
+                        .map(|year| self.number_width(year.repo_count, false, false)),
+                ),
+            ],
+            total_width - 10,
+        );
+        let year_cols: Vec<_> = (0..year_widths.len())
+            .map(|col| 10 + year_widths[..col].iter().sum::<i32>())
+            .collect();
+        let total_height = stats_header_height
+            + (rows.len() + 1 + stats.years.len()) as i32 * row_height
+            + stats_footer_height;
 
-        let mut document = Document::new()
-            .set("style", "background-color: white")
-            .set("xmlns", "http://www.w3.org/2000/svg")
-            .set("preserveAspectRatio", "xMidYMin meet")
-            .set("viewBox", format!("0 0 {total_width} {total_height}"));
+        let mut document = self.create_document(total_width, total_height);
 
         document = document.add(self.create_stats_header(
             total_width,
             stats_header_height,
-            "Repositories contributed to",
+            "Contribution summary",
             author,
             PULL_REQUEST_ICON_PATH,
         ));
 
-        // Header row
-        document = document.add(self.create_rect(
-            0,
-            stats_header_height,
-            total_width,
-            row_height,
-            &self.header_bg,
-        ));
-
-        // Header texts
-        let headers = ["No.", "Name", "Stars", "First PR", "Last PR", "PR Count"];
-        let mut x = 10;
-        for (i, header) in headers.iter().enumerate() {
-            document = document.add(self.create_header_text(
-                x,
-                stats_header_height + row_height / 2,
-                header,
+        let mut y = stats_header_height;
+        for (id, (label, value)) in rows.iter().enumerate() {
+            let bg_color = if id % 2 == 0 {
+                ThemeColor::Row
+            } else {
+                ThemeColor::AltRow
+            };
+            document = document.add(self.create_rect(0, y, total_width, row_height, bg_color));
+            document = document.add(self.create_header_text(10, y + row_height / 2, label));
+            let value = self.truncate_text(value, total_width - value_x);
+            document = document.add(self.create_text(
+                value_x,
+                y + row_height / 2,
+                &value,
+                ThemeColor::Text,
             ));
-            x += col_widths[i];
+            y += row_height;
         }
 
-        // Data rows
-        let mut y = stats_header_height + row_height;
-        for (id, repo) in repos.iter().enumerate() {
+        // PRs per year, newest first
+        document =
+            document.add(self.create_rect(0, y, total_width, row_height, ThemeColor::Header));
+        for (x, header) in year_cols.iter().zip(year_headers) {
+            document = document.add(self.create_header_text(*x, y + row_height / 2, header));
+        }
+        y += row_height;
+        for (id, (year, year_stats)) in stats.years.iter().rev().enumerate() {
             let bg_color = if id % 2 == 0 {
-                &self.row_bg_even
+                ThemeColor::Row
             } else {
-                &self.row_bg_odd
+                ThemeColor::AltRow
             };
-
             document = document.add(self.create_rect(0, y, total_width, row_height, bg_color));
-
-            let mut x = 10;
-            // No.
             document = document.add(self.create_text(
-                x,
+                year_cols[0],
                 y + row_height / 2,
-                &(id + 1).to_string(),
-                &self.text_color,
+                &year.to_string(),
+                ThemeColor::Text,
             ));
+            for (x, count, color) in [
+                (year_cols[1], year_stats.pr_count, ThemeColor::Pr),
+                (year_cols[2], year_stats.merged_count, ThemeColor::Merged),
+                (year_cols[3], year_stats.repo_count, ThemeColor::Text),
+            ] {
+                document = document.add(self.create_number_with_effect(
+                    x,
+                    y + row_height / 2,
+                    count,
+                    color,
+                    false,
+                    false,
+                ));
+            }
+            y += row_height;
+        }
 
-            // Name with link
-            x += col_widths[0];
-            let truncated_name = self.truncate_text(&repo.full_name, col_widths[1]);
-            document = document.add(self.create_link(
-                x,
-                y + row_height / 2,
-                &truncated_name,
-                &format!("https://github.com/{}", repo.full_name),
-            ));
+        document = document.add(self.create_stats_footer(total_width, y + stats_footer_height / 2));
 
-            // Stars
-            x += col_widths[1];
-            document = document.add(self.create_number_with_effect(
-                x,
-                y + row_height / 2,
-                repo.stargazer_count,
-                &self.star_color,
-                true,
-                false,
-            ));
+        output.push_str(&document.to_string());
+    }
+}
 
-            // First PR
-            x += col_widths[2];
-            document = document.add(self.create_link(
-                x,
-                y + row_height / 2,
-                &repo.first_pr.created_at.format("%Y-%m-%d").to_string(),
-                &repo.first_pr.url,
-            ));
+impl SvgRenderer {
+    /// Renders a stacked bar of the primary languages of `repos`, with a legend below it.
+    pub fn render_languages(
+        &self,
+        output: &mut String,
+        repos: &[Repository],
+        weight: LanguageWeight,
+        author: &str,
+    ) {
+        let max_languages = 10;
+        let mut languages = language_breakdown(repos, weight);
+        if languages.len() > max_languages {
+            let other = languages
+                .drain(max_languages - 1..)
+                .map(|(_, count)| count)
+                .sum();
+            languages.push(("Other".to_string(), other));
+        }
+        let total: u32 = languages.iter().map(|(_, count)| count).sum();
 
-            // Last PR
-            x += col_widths[3];
-            document = document.add(self.create_link(
-                x,
-                y + row_height / 2,
-                &repo.last_pr.created_at.format("%Y-%m-%d").to_string(),
-                &repo.last_pr.url,
+        let padding = 20;
+        let bar_height = 12;
+        let row_height = 32;
+        let stats_header_height = STATS_HEADER_HEIGHT;
+        let stats_footer_height = STATS_FOOTER_HEIGHT;
+        let total_width = self.width;
+        let column_width = total_width / 2;
+        let bar_y = stats_header_height + 8;
+        let legend_y = bar_y + bar_height + 12;
+        let legend_rows = languages.len().div_ceil(2).max(1) as i32;
+        let total_height = legend_y + legend_rows * row_height + stats_footer_height;
+
+        let mut document = self.create_document(total_width, total_height);
+
+        let names: Vec<_> = languages.iter().map(|(name, _)| name.as_str()).collect();
+        let bar_width = total_width - 2 * padding;
+        // Rounds the ends of the bar however its segments are split.
+        let bar_clip = ClipPath::new().set("id", "language-bar").add(
+            Rectangle::new()
+                .set("x", padding)
+                .set("y", bar_y)
+                .set("width", bar_width)
+                .set("height", bar_height)
+                .set("rx", bar_height / 2),
+        );
+        document = document.add(self.create_language_defs(&names).add(bar_clip));
+
+        document = document.add(self.create_stats_header(
+            total_width,
+            stats_header_height,
+            "Languages",
+            author,
+            REPO_ICON_PATH,
+        ));
+
+        if total == 0 {
+            document = document.add(self.create_rect(
+                padding,
+                bar_y,
+                bar_width,
+                bar_height,
+                ThemeColor::AltRow,
             ));
+            document = document.add(self.create_text(
+                padding,
+                legend_y + row_height / 2,
+                "No languages to show",
+                ThemeColor::Text,
+            ));
+        }
 
-            // PR Count
-            x += col_widths[4];
-            let pr_link = format!(
-                "https://github.com/{}/pulls?q=is%3Apr+author%3A{}",
-                repo.full_name, author
+        let mut bar = Group::new().set("clip-path", "url(#language-bar)");
+        let mut x = f64::from(padding);
+        for (name, count) in &languages {
+            let width = f64::from(bar_width) * f64::from(*count) / f64::from(total);
+            bar = bar.add(
+                Rectangle::new()
+                    .set("x", x)
+                    .set("y", bar_y)
+                    .set("width", width)
+                    .set("height", bar_height)
+                    .set("fill", self.language_color(name)),
             );
+            x += width;
+        }
+        document = document.add(bar);
+
+        // Legend, in two columns filled left to right
+        for (i, (name, count)) in languages.iter().enumerate() {
+            let x = padding + (i % 2) as i32 * column_width;
+            let y = legend_y + (i / 2) as i32 * row_height + row_height / 2;
+            document = match self.create_language_icon(x, y, name) {
+                Some(icon) => document.add(icon),
+                None => document.add(
+                    Circle::new()
+                        .set("cx", x + 6)
+                        .set("cy", y)
+                        .set("r", 6)
+                        .set("fill", self.language_color(name)),
+                ),
+            };
+            let percent = f64::from(*count) * 100.0 / f64::from(total);
+            let share = format!("{percent:.1}% ({count} {weight})");
+            let name = self.truncate_text(
+                name,
+                column_width - 2 * padding - 22 - self.measure(&share, false),
+            );
+            document = document.add(self.create_text(x + 22, y, &name, ThemeColor::Text));
             document = document.add(
-                Anchor::new()
-                    .set("href", pr_link)
-                    .set("target", "_blank")
-                    .add(self.create_number_with_effect(
-                        x,
-                        y + row_height / 2,
-                        repo.pr_count,
-                        &self.pr_color,
-                        false,
-                        false,
-                    )),
+                self.create_text(x + column_width - 2 * padding, y, &share, ThemeColor::Text)
+                    .set("text-anchor", "end")
+                    .set("opacity", 0.72),
             );
-
-            y += row_height;
         }
 
-        // Total row
-        document =
-            document.add(self.create_rect(0, y, total_width, row_height, &self.total_row_bg));
+        document = document
+            .add(self.create_stats_footer(total_width, total_height - stats_footer_height / 2));
 
-        document = document.add(
-            self.create_text(10, y + row_height / 2, "Total", &self.text_color)
-                .set("font-weight", "bold"),
-        );
+        output.push_str(&document.to_string());
+    }
+
+    /// Renders a monthly histogram of the PRs of `repos`, stacking the `top_repos` with most PRs.
+    pub fn render_timeline(
+        &self,
+        output: &mut String,
+        repos: &[ContributedRepo],
+        top_repos: usize,
+        author: &str,
+    ) {
+        let repos = self.private_repos.contributed(repos);
+        let timeline = Timeline::new(&repos, top_repos);
+        let totals = timeline.totals();
+        let max = totals.iter().copied().max().unwrap_or(0).max(1);
+
+        let padding = 20;
+        let axis_width = 30;
+        let chart_height = 160;
+        let label_height = 28;
+        let legend_row_height = 24;
+        let legend_columns = 3;
+        let stats_header_height = STATS_HEADER_HEIGHT;
+        let stats_footer_height = STATS_FOOTER_HEIGHT;
+        let total_width = self.width;
+        let chart_left = padding + axis_width;
+        let chart_width = total_width - chart_left - padding;
+        let chart_top = stats_header_height + 12;
+        let chart_bottom = chart_top + chart_height;
+        let legend_rows = if timeline.series.len() > 1 {
+            timeline.series.len().div_ceil(legend_columns) as i32
+        } else {
+            0
+        };
+        let legend_y = chart_bottom + label_height;
+        let total_height = legend_y + legend_rows * legend_row_height + stats_footer_height;
+
+        let mut document = self.create_document(total_width, total_height);
 
-        let total_prs: u32 = repos.iter().map(|x| x.pr_count).sum();
-        let x_prs =
-            10 + col_widths[0] + col_widths[1] + col_widths[2] + col_widths[3] + col_widths[4];
-        document = document.add(self.create_number_with_effect(
-            x_prs,
-            y + row_height / 2,
-            total_prs,
-            &self.pr_color,
-            false,
-            true,
+        document = document.add(self.create_stats_header(
+            total_width,
+            stats_header_height,
+            "Pull requests over time",
+            author,
+            PULL_REQUEST_ICON_PATH,
         ));
 
+        // Axis with the busiest month at the top
+        for (y, label) in [(chart_top, max), (chart_bottom, 0)] {
+            document = document.add(
+                Rectangle::new()
+                    .set("x", chart_left)
+                    .set("y", y)
+                    .set("width", chart_width)
+                    .set("height", 1)
+                    .fill(self, ThemeColor::Header),
+            );
+            document = document.add(
+                self.create_text(chart_left - 8, y, &label.to_string(), ThemeColor::Text)
+                    .set("font-size", 12)
+                    .set("text-anchor", "end"),
+            );
+        }
+
+        if timeline.months.is_empty() {
+            document = document.add(
+                self.create_text(
+                    chart_left + chart_width / 2,
+                    chart_top + chart_height / 2,
+                    "No pull requests to show",
+                    ThemeColor::Text,
+                )
+                .set("text-anchor", "middle"),
+            );
+        }
+
+        let palette = [
+            ThemeColor::Pr,
+            ThemeColor::Merged,
+            ThemeColor::Link,
+            ThemeColor::Star,
+            ThemeColor::Fork,
+            ThemeColor::Review,
+            ThemeColor::Deletion,
+            ThemeColor::Answer,
+        ];
+        let is_other = |i: usize| timeline.series.len() > 1 && i == top_repos;
+        let fill_series = |rect: Rectangle, i: usize| {
+            if is_other(i) {
+                rect.set("fill", OTHER_LANGUAGE_COLOR)
+            } else {
+                rect.fill(self, palette[i % palette.len()])
+            }
+        };
+
+        let slot = f64::from(chart_width) / timeline.months.len().max(1) as f64;
+        let gap = if slot > 4.0 { slot * 0.2 } else { 0.0 };
+        let mut last_label_x = f64::NEG_INFINITY;
+        for (i, month) in timeline.months.iter().enumerate() {
+            let x = f64::from(chart_left) + slot * i as f64;
+            let mut y = f64::from(chart_bottom);
+            for (id, series) in timeline.series.iter().enumerate() {
+                let count = series.counts[i];
+                if count == 0 {
+                    continue;
+                }
+                let height = f64::from(chart_height) * f64::from(count) / f64::from(max);
+                y -= height;
+                document = document.add(fill_series(
+                    Rectangle::new()
+                        .set("x", x + gap / 2.0)
+                        .set("y", y)
+                        .set("width", slot - gap)
+                        .set("height", height),
+                    id,
+                ));
+            }
+
+            // Label the first month and each January, as long as they don't overlap
+            if (i == 0 || month.month == 1) && x - last_label_x >= 40.0 {
+                document = document.add(
+                    Text::new(month.year.to_string())
+                        .set("x", x)
+                        .set("y", chart_bottom + label_height / 2)
+                        .fill(self, ThemeColor::Text)
+                        .set("font-family", self.font_family.as_str())
+                        .set("font-size", 12)
+                        .set("dominant-baseline", "middle"),
+                );
+                last_label_x = x;
+            }
+        }
+
+        // Legend, only when the bars are stacked
+        if legend_rows > 0 {
+            let column_width = (total_width - 2 * padding) / legend_columns as i32;
+            for (id, series) in timeline.series.iter().enumerate() {
+                let x = padding + (id % legend_columns) as i32 * column_width;
+                let y = legend_y
+                    + (id / legend_columns) as i32 * legend_row_height
+                    + legend_row_height / 2;
+                document = document.add(fill_series(
+                    Rectangle::new()
+                        .set("x", x)
+                        .set("y", y - 6)
+                        .set("width", 12)
+                        .set("height", 12)
+                        .set("rx", 4),
+                    id,
+                ));
+                let name = self.truncate_text(&series.name, column_width - 20);
+                document = document.add(self.create_text(x + 20, y, &name, ThemeColor::Text));
+            }
+        }
+
         document = document
-            .add(self.create_stats_footer(total_width, y + row_height + stats_footer_height / 2));
+            .add(self.create_stats_footer(total_width, total_height - stats_footer_height / 2));
 
         output.push_str(&document.to_string());
     }
@@ -904,7 +2230,8 @@ impl Render for SvgRenderer {
 mod tests {
     use super::*;
     use crate::github::{
-        ContributedRepo, PrimaryLanguage, PullRequest, Repository, RepositoryWithStargazerCount,
+        CommitCount, ContributedRepo, ContributionKind, PrimaryLanguage, PullRequest,
+        PullRequestRepository, PullRequestState, RepoContributions, Repository, RepositoryOwner,
     };
     use chrono::{TimeZone, Utc};
     use std::fs;
@@ -919,36 +2246,73 @@ mod tests {
     ) -> Repository {
         Repository {
             name_with_owner: format!("test-user/{}", name),
+            url: format!("https://github.com/test-user/{}", name),
             stargazer_count: stars,
             fork_count: forks,
             primary_language: Some(PrimaryLanguage {
                 name: language.to_string(),
             }),
+            description: None,
             is_archived: archived,
+            is_private: false,
             created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             pushed_at: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
         }
     }
 
+    fn create_test_pull_request(url: &str, year: i32, stars: u32) -> PullRequest {
+        PullRequest {
+            url: url.to_string(),
+            created_at: Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap(),
+            state: PullRequestState::Merged,
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            commits: CommitCount { total_count: 0 },
+            repository: PullRequestRepository {
+                name_with_owner: url
+                    .trim_start_matches("https://github.com/")
+                    .split("/pull/")
+                    .next()
+                    .unwrap()
+                    .to_string(),
+                stargazer_count: stars,
+                fork_count: 0,
+                primary_language: None,
+                description: None,
+                is_archived: false,
+                is_private: false,
+                owner: RepositoryOwner {
+                    avatar_url: String::new(),
+                },
+            },
+        }
+    }
+
     fn create_test_contributed_repo(name: &str, stars: u32, prs: u32) -> ContributedRepo {
         ContributedRepo {
             full_name: name.to_string(),
+            html_url: format!("https://github.com/{}", name),
             stargazer_count: stars,
+            fork_count: 0,
+            primary_language: Some(PrimaryLanguage {
+                name: "Rust".to_string(),
+            }),
+            description: None,
+            is_archived: false,
+            is_private: false,
+            owner_avatar_url: String::new(),
             pr_count: prs,
-            first_pr: PullRequest {
-                url: "https://github.com/first".to_string(),
-                created_at: Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
-                repository: RepositoryWithStargazerCount {
-                    stargazer_count: stars,
-                },
-            },
-            last_pr: PullRequest {
-                url: "https://github.com/last".to_string(),
-                created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
-                repository: RepositoryWithStargazerCount {
-                    stargazer_count: stars,
-                },
-            },
+            merged_count: prs,
+            open_count: 0,
+            closed_count: 0,
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            commit_count: 0,
+            first_pr: create_test_pull_request("https://github.com/first", 2023, stars),
+            last_pr: create_test_pull_request("https://github.com/last", 2024, stars),
+            pr_dates: vec![Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(); prs as usize],
         }
     }
 
@@ -1046,4 +2410,404 @@ mod tests {
         renderer.render_contributed_repos(&mut contributed_output, &contributed_repos, "test-user");
         assert!(contributed_output.contains(r#"viewBox="0 0 780 "#));
     }
+
+    #[test]
+    fn svg_renderer_sizes_columns_to_content_and_width() {
+        // Short columns leave room for the whole name.
+        let name = "a-fairly-long-repository-name-for-a-small-tool";
+        let repos = vec![create_test_repo(name, "Rust", 10, 1, false)];
+        let mut output = String::new();
+        SvgRenderer::new().render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.contains(&format!("\n{name}\n")));
+
+        let mut narrow = String::new();
+        SvgRenderer::new()
+            .with_width(500)
+            .render_created_repos(&mut narrow, &repos, "test-user");
+        assert!(narrow.contains(r#"viewBox="0 0 500 "#));
+        assert!(!narrow.contains(&format!("\n{name}\n")));
+
+        let mut compact = String::new();
+        SvgRenderer::new()
+            .with_width(10)
+            .with_compact(true)
+            .render_created_repos(&mut compact, &repos, "test-user");
+        let height = 56 + 3 * COMPACT_ROW_HEIGHT + 26;
+        assert!(compact.contains(&format!(r#"viewBox="0 0 400 {height}""#)));
+    }
+
+    #[test]
+    fn svg_renderer_truncates_multi_byte_names() {
+        let renderer = SvgRenderer::new();
+        let name = "用户/这是一个名字非常非常长的中文仓库用来测试截断是否会在字符中间切开";
+        let contributed_repos = vec![create_test_contributed_repo(name, 10, 1)];
+
+        let mut output = String::new();
+        renderer.render_contributed_repos(&mut output, &contributed_repos, "test-user");
+        // The link keeps the full name, the text is cut short.
+        assert!(output.contains(&format!("https://github.com/{name}\"")));
+        assert!(
+            output.contains("\n用户/这是一个名字非常非常长的中文仓库用来测试截断是否会在...\n")
+        );
+    }
+
+    #[test]
+    fn svg_renderer_links_profile_to_web_url() {
+        let renderer = SvgRenderer::new().with_web_url("https://ghe.example.com/");
+        let repos = vec![create_test_repo("repo-rust", "Rust", 1, 0, false)];
+
+        let mut output = String::new();
+        renderer.render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.contains(r#"href="https://ghe.example.com/test-user""#));
+    }
+
+    #[test]
+    fn markdown_renderer_shows_owner_of_foreign_repos() {
+        let mut foreign = create_test_repo("tool", "Rust", 5, 1, false);
+        foreign.name_with_owner = "acme/tool".to_string();
+        let repos = vec![create_test_repo("mine", "Rust", 10, 2, false), foreign];
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("[mine]("));
+        assert!(output.contains("[acme/tool]("));
+    }
+
+    #[test]
+    fn renderers_show_selected_columns_in_order() {
+        let mut repo = create_test_repo("tool", "Rust", 12, 3, false);
+        repo.description = Some("Does | things".to_string());
+        let repos = vec![repo];
+        let columns = vec![
+            Column::Name,
+            Column::Stars,
+            Column::Language,
+            Column::Description,
+        ];
+
+        let mut output = String::new();
+        MarkdownRenderer::new()
+            .with_columns(columns.clone())
+            .render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.starts_with("| Name "));
+        assert!(output.contains("| Stars | Language | Description    |"));
+        assert!(output.contains("|    12 | Rust     | Does \\| things |"));
+        assert!(output.contains("\n| Total "));
+        assert!(output.contains("|    12 |          |                |"));
+        assert!(!output.contains("No."));
+        assert!(!output.contains("Last Update"));
+
+        let mut output = String::new();
+        SvgRenderer::new()
+            .with_columns(columns.clone())
+            .render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Does | things"));
+        assert!(!output.contains("No."));
+        assert!(!output.contains("Last Update"));
+
+        // Contributed repos keep the selected columns they have data for.
+        let contributed = vec![create_test_contributed_repo("org/repo1", 1000, 20)];
+        let mut output = String::new();
+        MarkdownRenderer::new()
+            .with_columns(columns.clone())
+            .render_contributed_repos(&mut output, &contributed, "test-user");
+        assert!(output.starts_with("| Name "));
+        assert!(output.contains("| Stars | Language | Description |"));
+        assert!(!output.contains("No."));
+        assert!(!output.contains("PRs"));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_columns(vec![Column::Name, Column::Prs])
+            .render_contributed_repos(&mut output, &contributed, "test-user");
+        assert!(!output.contains("No."));
+        assert!(!output.contains(">First<"));
+        // Counts line up on the right, headers included.
+        assert!(output.contains("font-weight=\"bold\" text-anchor=\"end\""));
+
+        // Columns a table has no data for leave it with its default ones.
+        output.clear();
+        MarkdownRenderer::new()
+            .with_columns(vec![Column::Merged])
+            .render_created_repos(&mut output, &repos, "test-user");
+        assert!(output.starts_with("| No.   | Name "));
+        assert!(!output.contains("Merged"));
+
+        assert_eq!("updated".parse(), Ok(Column::LastUpdate));
+        assert_eq!("lines_changed".parse(), Ok(Column::LinesChanged));
+        assert!("owner".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn renderers_show_merged_column_on_request() {
+        let mut repo = create_test_contributed_repo("org/repo1", 1000, 20);
+        repo.merged_count = 17;
+        let repos = vec![repo];
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(!output.contains("Merged"));
+
+        let columns = vec![Column::Name, Column::Prs, Column::Merged];
+        output.clear();
+        MarkdownRenderer::new()
+            .with_columns(columns.clone())
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Merged"));
+        assert!(output.contains(
+            "[17](https://github.com/org/repo1/pulls?q=is%3Apr+is%3Amerged+author%3Atest-user)"
+        ));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_columns(columns)
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Merged"));
+        assert!(output.contains(r#"viewBox="0 0 780 "#));
+    }
+
+    #[test]
+    fn renderers_show_lines_changed_column_on_request() {
+        let mut repo = create_test_contributed_repo("org/repo1", 1000, 20);
+        repo.additions = 1200;
+        repo.deletions = 340;
+        let repos = vec![repo];
+
+        let mut output = String::new();
+        MarkdownRenderer::new()
+            .with_columns(vec![Column::Name, Column::LinesChanged])
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Lines Changed"));
+        assert!(output.contains("+1200 / -340"));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_columns(vec![Column::Name, Column::Merged, Column::LinesChanged])
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Lines Changed"));
+        assert!(output.contains("+1200"));
+        assert!(output.contains(r#"viewBox="0 0 780 "#));
+    }
+
+    #[test]
+    fn renderers_show_language_column_on_request() {
+        let mut archived = create_test_contributed_repo("org/old", 10, 1);
+        archived.is_archived = true;
+        let repos = vec![
+            create_test_contributed_repo("org/repo1", 1000, 20),
+            archived,
+        ];
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(!output.contains("Language"));
+        assert!(output.contains("~~[org/old](https://github.com/org/old)~~"));
+
+        output.clear();
+        let columns = vec![Column::Name, Column::Language, Column::Prs];
+        MarkdownRenderer::new()
+            .with_columns(columns.clone())
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Language"));
+        assert!(output.contains("Rust"));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_columns(columns)
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("Language"));
+        assert!(output.contains(r##"href="#lang-rust""##));
+    }
+
+    #[test]
+    fn renderers_render_summary_and_years() {
+        let prs = vec![
+            create_test_pull_request("https://github.com/org/repo1/pull/1", 2023, 10),
+            create_test_pull_request("https://github.com/org/repo2/pull/2", 2024, 20),
+        ];
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let stats = ContributionStats::new(&prs, &[], now);
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_summary(&mut output, &stats, "test-user");
+        assert!(output.contains("2 (2 merged)"));
+        assert!(output.contains("2023-01-01"));
+        assert!(output.contains("| 2024 | 1   | 1      | 1     |"));
+
+        output.clear();
+        SvgRenderer::new().render_summary(&mut output, &stats, "test-user");
+        assert!(output.contains("Contribution summary"));
+        assert!(output.contains("2023-01-01"));
+    }
+
+    #[test]
+    fn svg_renderer_renders_language_breakdown() {
+        let repos: Vec<_> = ["Rust", "Rust", "Go", "Fortran"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, language)| create_test_repo(&format!("user/repo{i}"), language, 10, 0, false))
+            .collect();
+
+        let mut output = String::new();
+        SvgRenderer::new().render_languages(
+            &mut output,
+            &repos,
+            LanguageWeight::Repos,
+            "test-user",
+        );
+        assert!(output.contains("Languages"));
+        assert!(output.contains(r##"fill="#DEA584""##));
+        assert!(output.contains("50.0% (2 repos)"));
+        // Fortran has no icon, only its fallback colour
+        assert!(output.contains(&format!(r#"fill="{OTHER_LANGUAGE_COLOR}""#)));
+
+        output.clear();
+        SvgRenderer::new().render_languages(&mut output, &[], LanguageWeight::Stars, "test-user");
+        assert!(output.contains("No languages to show"));
+    }
+
+    #[test]
+    fn svg_renderer_renders_timeline() {
+        let mut repo1 = create_test_contributed_repo("org/repo1", 100, 3);
+        repo1.pr_dates = ["2023-11-05", "2024-01-10", "2024-01-20"]
+            .iter()
+            .map(|date| format!("{date}T00:00:00Z").parse().unwrap())
+            .collect();
+        let mut repo2 = create_test_contributed_repo("org/repo2", 10, 1);
+        repo2.pr_dates = vec![Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()];
+        let repos = [repo1, repo2];
+
+        let mut output = String::new();
+        SvgRenderer::new().render_timeline(&mut output, &repos, 0, "test-user");
+        assert!(output.contains("Pull requests over time"));
+        assert!(output.contains("2023") && output.contains("2024"));
+        assert!(!output.contains("org/repo1"));
+
+        output.clear();
+        SvgRenderer::new().render_timeline(&mut output, &repos, 1, "test-user");
+        assert!(output.contains("org/repo1"));
+        assert!(output.contains("Other"));
+
+        output.clear();
+        SvgRenderer::new().render_timeline(&mut output, &[], 0, "test-user");
+        assert!(output.contains("No pull requests to show"));
+    }
+
+    #[test]
+    fn svg_renderer_draws_with_theme_colors() {
+        let repos = vec![create_test_contributed_repo("org/repo1", 1000, 20)];
+
+        let mut output = String::new();
+        SvgRenderer::new().render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("background-color: #FFFFFF"));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_theme(Theme::dark())
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("background-color: #0D1117"));
+        assert!(output.contains(r##"fill="#C9D1D9""##));
+        assert!(!output.contains(&Theme::light().text_color));
+    }
+
+    #[test]
+    fn svg_renderer_follows_color_scheme_with_dark_theme() {
+        let repos = vec![create_test_contributed_repo("org/repo1", 1000, 20)];
+
+        let mut output = String::new();
+        SvgRenderer::new()
+            .with_dark_theme(Some(Theme::dracula()))
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("<style>"));
+        assert!(output.contains(".text_color { fill: #334155 }"));
+        assert!(
+            output.contains(
+                "@media (prefers-color-scheme: dark) { svg { background-color: #282A36 }"
+            )
+        );
+        assert!(output.contains(r#"class="text_color""#));
+        assert!(!output.contains(r##"fill="#334155""##));
+        assert!(!output.contains("style=\"background-color"));
+    }
+
+    #[test]
+    fn renderers_show_one_column_per_contribution_kind() {
+        let repos = vec![RepoContributions {
+            full_name: "org/repo1".to_string(),
+            html_url: "https://github.com/org/repo1".to_string(),
+            stargazer_count: 1000,
+            is_private: false,
+            counts: [(ContributionKind::Review, 12), (ContributionKind::Issue, 3)].into(),
+            first_contribution_at: Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+            last_contribution_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        }];
+        let kinds = [ContributionKind::Issue, ContributionKind::Review];
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_contributions(&mut output, &repos, &kinds, "test-user");
+        assert!(output.contains("Issues"));
+        assert!(
+            output.contains(
+                "[3](https://github.com/org/repo1/issues?q=is%3Aissue+author%3Atest-user)"
+            )
+        );
+        assert!(output.contains(
+            "[12](https://github.com/org/repo1/pulls?q=is%3Apr+reviewed-by%3Atest-user+-author%3Atest-user)"
+        ));
+
+        output.clear();
+        SvgRenderer::new().render_contributions(
+            &mut output,
+            &repos,
+            &[ContributionKind::Review],
+            "test-user",
+        );
+        assert!(output.contains("Pull requests reviewed"));
+        assert!(output.contains("Reviews"));
+        assert!(!output.contains("Issues"));
+        assert!(output.contains(r#"viewBox="0 0 780 "#));
+
+        // Selected kind columns keep their place, the other kinds follow.
+        output.clear();
+        MarkdownRenderer::new()
+            .with_columns(vec![Column::Reviews, Column::Name])
+            .render_contributions(&mut output, &repos, &kinds, "test-user");
+        let header = output.lines().next().unwrap();
+        let position = |title| header.find(title).unwrap();
+        assert!(position("Reviews") < position("Name") && position("Name") < position("Issues"));
+        assert!(!output.contains("No."));
+    }
+
+    #[test]
+    fn renderers_anonymize_private_repos_on_request() {
+        let mut secret = create_test_contributed_repo("acme/secret", 3, 4);
+        secret.is_private = true;
+        let mut internal = create_test_contributed_repo("acme/internal", 2, 5);
+        internal.is_private = true;
+        let repos = vec![
+            secret,
+            create_test_contributed_repo("org/public", 100, 1),
+            internal,
+        ];
+
+        let mut output = String::new();
+        MarkdownRenderer::new().render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(output.contains("acme/secret"));
+
+        output.clear();
+        MarkdownRenderer::new()
+            .with_private_repos(PrivateRepos::Anonymize)
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(!output.contains("acme"));
+        assert!(output.contains("[org/public](https://github.com/org/public)"));
+        assert!(output.contains(" 2 private repositories "));
+        assert!(!output.contains("[2 private repositories]"));
+
+        output.clear();
+        SvgRenderer::new()
+            .with_private_repos(PrivateRepos::Anonymize)
+            .render_contributed_repos(&mut output, &repos, "test-user");
+        assert!(!output.contains("acme"));
+        assert!(output.contains("2 private repositories"));
+    }
 }
@@ -0,0 +1,480 @@
+//! Activity summaries computed from fetched PRs and repositories.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::github::{ContributedRepo, PullRequest, PullRequestState, Repository};
+
+/// A calendar month, ordered chronologically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Month {
+    pub year: i32,
+    /// 1 for January.
+    pub month: u32,
+}
+
+impl Month {
+    #[must_use]
+    pub fn of(time: DateTime<Utc>) -> Self {
+        Self {
+            year: time.year(),
+            month: time.month(),
+        }
+    }
+
+    #[must_use]
+    pub fn next(self) -> Self {
+        if self.month == 12 {
+            Self {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Self {
+                year: self.year,
+                month: self.month + 1,
+            }
+        }
+    }
+}
+
+/// Formats as `YYYY-MM`.
+impl std::fmt::Display for Month {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Consecutive months with at least one PR each.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Streak {
+    pub start: Month,
+    pub end: Month,
+    pub months: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct YearStats {
+    pub pr_count: u32,
+    pub merged_count: u32,
+    /// Distinct repositories PRs were opened in.
+    pub repo_count: u32,
+}
+
+/// Aggregates over a user's PRs and created repositories.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContributionStats {
+    pub pr_count: u32,
+    pub merged_count: u32,
+    /// Distinct repositories PRs were opened in.
+    pub repo_count: u32,
+    pub first_contribution_at: Option<DateTime<Utc>>,
+    /// Only months with PRs have an entry.
+    pub prs_per_month: BTreeMap<Month, u32>,
+    /// Only years with PRs have an entry.
+    pub years: BTreeMap<i32, YearStats>,
+    pub longest_streak: Option<Streak>,
+    /// The streak running through this or last month, which may still get PRs.
+    pub current_streak: Option<Streak>,
+    /// Languages of the repositories PRs were opened in, most PRs first.
+    pub top_languages: Vec<(String, u32)>,
+    pub created_repo_count: u32,
+    pub created_star_count: u32,
+    pub created_fork_count: u32,
+}
+
+impl ContributionStats {
+    /// Summarizes `prs` and `created` repositories as of `now`.
+    #[must_use]
+    pub fn new(prs: &[PullRequest], created: &[Repository], now: DateTime<Utc>) -> Self {
+        let count = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        let is_merged = |pr: &&PullRequest| pr.state == PullRequestState::Merged;
+
+        let mut prs_per_month = BTreeMap::new();
+        let mut repos_per_year: BTreeMap<i32, BTreeSet<&str>> = BTreeMap::new();
+        let mut years: BTreeMap<i32, YearStats> = BTreeMap::new();
+        let mut languages: HashMap<&str, u32> = HashMap::new();
+        for pr in prs {
+            *prs_per_month.entry(Month::of(pr.created_at)).or_default() += 1;
+            let year = years.entry(pr.created_at.year()).or_default();
+            year.pr_count += 1;
+            year.merged_count += u32::from(is_merged(&pr));
+            repos_per_year
+                .entry(pr.created_at.year())
+                .or_default()
+                .insert(&pr.repository.name_with_owner);
+            if let Some(language) = &pr.repository.primary_language {
+                *languages.entry(&language.name).or_default() += 1;
+            }
+        }
+        for (year, repos) in repos_per_year {
+            years.entry(year).or_default().repo_count = count(repos.len());
+        }
+
+        let top_languages = sorted_by_count(languages);
+
+        let streaks = streaks(prs_per_month.keys().copied());
+        let this_month = Month::of(now);
+        let current_streak = streaks
+            .last()
+            .filter(|streak| streak.end == this_month || streak.end.next() == this_month)
+            .copied();
+        // The earliest of equally long streaks.
+        let longest_streak = streaks
+            .iter()
+            .copied()
+            .min_by_key(|streak| (Reverse(streak.months), streak.start));
+
+        Self {
+            pr_count: count(prs.len()),
+            merged_count: count(prs.iter().filter(is_merged).count()),
+            repo_count: count(
+                prs.iter()
+                    .map(|pr| pr.repository.name_with_owner.as_str())
+                    .collect::<BTreeSet<_>>()
+                    .len(),
+            ),
+            first_contribution_at: prs.iter().map(|pr| pr.created_at).min(),
+            prs_per_month,
+            years,
+            longest_streak,
+            current_streak,
+            top_languages,
+            created_repo_count: count(created.len()),
+            created_star_count: created.iter().map(|repo| repo.stargazer_count).sum(),
+            created_fork_count: created.iter().map(|repo| repo.fork_count).sum(),
+        }
+    }
+}
+
+/// PRs per month of a [`Timeline`] opened in one repository, or in several.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelineSeries {
+    pub name: String,
+    /// One count per month of the timeline.
+    pub counts: Vec<u32>,
+}
+
+/// PRs per month, from the month of the first PR to that of the last.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Timeline {
+    pub months: Vec<Month>,
+    /// The repositories with most PRs, then one series with all others.
+    /// A single series when the timeline isn't stacked.
+    pub series: Vec<TimelineSeries>,
+}
+
+impl Timeline {
+    /// Counts the PRs of `repos` per month, stacking the `top_repos` with most PRs.
+    #[must_use]
+    pub fn new(repos: &[ContributedRepo], top_repos: usize) -> Self {
+        let dates = || repos.iter().flat_map(|repo| &repo.pr_dates).copied();
+        let (Some(first), Some(last)) = (dates().min(), dates().max()) else {
+            return Self::default();
+        };
+        let mut months = vec![Month::of(first)];
+        while let Some(&month) = months.last()
+            && month < Month::of(last)
+        {
+            months.push(month.next());
+        }
+        let counts = |repos: &[&ContributedRepo]| {
+            let mut counts = vec![0; months.len()];
+            for date in repos.iter().flat_map(|repo| &repo.pr_dates) {
+                if let Ok(i) = months.binary_search(&Month::of(*date)) {
+                    counts[i] += 1;
+                }
+            }
+            counts
+        };
+
+        let mut ranked: Vec<_> = repos.iter().collect();
+        ranked.sort_by_key(|repo| Reverse(repo.pr_dates.len()));
+        let (top, rest) = ranked.split_at(top_repos.min(ranked.len()));
+        let mut series: Vec<_> = top
+            .iter()
+            .map(|repo| TimelineSeries {
+                name: repo.full_name.clone(),
+                counts: counts(&[repo]),
+            })
+            .collect();
+        if !rest.is_empty() {
+            series.push(TimelineSeries {
+                name: if top.is_empty() {
+                    "All repositories".to_string()
+                } else {
+                    "Other".to_string()
+                },
+                counts: counts(rest),
+            });
+        }
+
+        Self { months, series }
+    }
+
+    /// The PRs of each month, across all series.
+    #[must_use]
+    pub fn totals(&self) -> Vec<u32> {
+        (0..self.months.len())
+            .map(|i| self.series.iter().map(|series| series.counts[i]).sum())
+            .collect()
+    }
+}
+
+/// What each repository adds to its language in [`language_breakdown`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum LanguageWeight {
+    /// One per repository.
+    #[default]
+    Repos,
+    /// The repository's stars, so that unstarred repositories don't count.
+    Stars,
+}
+
+impl LanguageWeight {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LanguageWeight::Repos => "repos",
+            LanguageWeight::Stars => "stars",
+        }
+    }
+}
+
+impl std::fmt::Display for LanguageWeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LanguageWeight {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "repos" | "count" => Ok(LanguageWeight::Repos),
+            "stars" => Ok(LanguageWeight::Stars),
+            _ => Err(format!(
+                "unknown language weight '{s}', expected repos or stars"
+            )),
+        }
+    }
+}
+
+/// Totals the `weight` of `repos` per primary language, heaviest first.
+///
+/// Repositories without a primary language are left out.
+#[must_use]
+pub fn language_breakdown(repos: &[Repository], weight: LanguageWeight) -> Vec<(String, u32)> {
+    let mut languages: HashMap<&str, u32> = HashMap::new();
+    for repo in repos {
+        let Some(language) = &repo.primary_language else {
+            continue;
+        };
+        *languages.entry(&language.name).or_default() += match weight {
+            LanguageWeight::Repos => 1,
+            LanguageWeight::Stars => repo.stargazer_count,
+        };
+    }
+    sorted_by_count(languages)
+}
+
+/// Drops the zero counts and sorts by count descending, then by name.
+fn sorted_by_count(counts: HashMap<&str, u32>) -> Vec<(String, u32)> {
+    let mut counts: Vec<_> = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| Reverse(a.1).cmp(&Reverse(b.1)).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Splits ascending `months` into runs of consecutive months.
+fn streaks(months: impl Iterator<Item = Month>) -> Vec<Streak> {
+    let mut streaks: Vec<Streak> = Vec::new();
+    for month in months {
+        match streaks.last_mut() {
+            Some(streak) if streak.end.next() == month => {
+                streak.end = month;
+                streak.months += 1;
+            }
+            _ => streaks.push(Streak {
+                start: month,
+                end: month,
+                months: 1,
+            }),
+        }
+    }
+    streaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{CommitCount, PrimaryLanguage, PullRequestRepository, RepositoryOwner};
+    use chrono::TimeZone;
+
+    fn pr(repo: &str, language: &str, created_at: &str, state: PullRequestState) -> PullRequest {
+        PullRequest {
+            url: format!("https://github.com/{repo}/pull/1"),
+            created_at: created_at.parse().unwrap(),
+            state,
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            commits: CommitCount { total_count: 1 },
+            repository: PullRequestRepository {
+                name_with_owner: repo.to_string(),
+                stargazer_count: 0,
+                fork_count: 0,
+                primary_language: Some(PrimaryLanguage {
+                    name: language.to_string(),
+                }),
+                description: None,
+                is_archived: false,
+                is_private: false,
+                owner: RepositoryOwner {
+                    avatar_url: String::new(),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn contribution_stats_counts_months_years_streaks_and_languages() {
+        use PullRequestState::{Merged, Open};
+        let prs = [
+            pr("rust-lang/rust", "Rust", "2024-11-20T00:00:00Z", Merged),
+            pr("rust-lang/rust", "Rust", "2024-12-01T00:00:00Z", Merged),
+            pr("golang/go", "Go", "2025-01-05T00:00:00Z", Open),
+            pr("tokio-rs/tokio", "Rust", "2025-01-06T00:00:00Z", Merged),
+            pr("golang/go", "Go", "2025-05-01T00:00:00Z", Merged),
+            pr("golang/go", "Go", "2025-06-01T00:00:00Z", Open),
+        ];
+        let now = "2025-07-15T00:00:00Z".parse().unwrap();
+        let stats = ContributionStats::new(&prs, &[], now);
+
+        assert_eq!(stats.pr_count, 6);
+        assert_eq!(stats.merged_count, 4);
+        assert_eq!(stats.repo_count, 3);
+        assert_eq!(
+            stats.first_contribution_at,
+            Some("2024-11-20T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            stats.prs_per_month.get(&Month {
+                year: 2025,
+                month: 1
+            }),
+            Some(&2)
+        );
+        assert_eq!(
+            stats.years[&2025],
+            YearStats {
+                pr_count: 4,
+                merged_count: 2,
+                repo_count: 2,
+            }
+        );
+
+        let longest = stats.longest_streak.unwrap();
+        assert_eq!(
+            (longest.start.to_string(), longest.months),
+            ("2024-11".to_string(), 3)
+        );
+        let current = stats.current_streak.unwrap();
+        assert_eq!(
+            (current.start.to_string(), current.months),
+            ("2025-05".to_string(), 2)
+        );
+        assert_eq!(
+            stats.top_languages,
+            [("Go".to_string(), 3), ("Rust".to_string(), 3)]
+        );
+
+        let later = "2025-09-01T00:00:00Z".parse().unwrap();
+        assert_eq!(
+            ContributionStats::new(&prs, &[], later).current_streak,
+            None
+        );
+    }
+
+    #[test]
+    fn language_breakdown_weighs_by_repos_or_stars() {
+        let repo = |language: Option<&str>, stars| Repository {
+            name_with_owner: "user/repo".to_string(),
+            url: "https://github.com/user/repo".to_string(),
+            stargazer_count: stars,
+            fork_count: 0,
+            primary_language: language.map(|name| PrimaryLanguage {
+                name: name.to_string(),
+            }),
+            description: None,
+            is_archived: false,
+            is_private: false,
+            created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            pushed_at: None,
+        };
+        let repos = [
+            repo(Some("Rust"), 10),
+            repo(Some("Go"), 50),
+            repo(Some("Rust"), 0),
+            repo(Some("Python"), 0),
+            repo(None, 100),
+        ];
+
+        assert_eq!(
+            language_breakdown(&repos, LanguageWeight::Repos),
+            [
+                ("Rust".to_string(), 2),
+                ("Go".to_string(), 1),
+                ("Python".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            language_breakdown(&repos, LanguageWeight::Stars),
+            [("Go".to_string(), 50), ("Rust".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn timeline_counts_months_and_stacks_top_repos() {
+        use crate::github::{ContributedReposOptions, group_pull_requests};
+        use PullRequestState::Merged;
+        let prs = [
+            pr("rust-lang/rust", "Rust", "2024-11-20T00:00:00Z", Merged),
+            pr("rust-lang/rust", "Rust", "2025-02-01T00:00:00Z", Merged),
+            pr("rust-lang/rust", "Rust", "2025-02-10T00:00:00Z", Merged),
+            pr("golang/go", "Go", "2025-01-05T00:00:00Z", Merged),
+            pr("tokio-rs/tokio", "Rust", "2025-02-06T00:00:00Z", Merged),
+        ];
+        let repos = group_pull_requests(&prs, &ContributedReposOptions::default());
+
+        let timeline = Timeline::new(&repos, 0);
+        let months: Vec<_> = timeline.months.iter().map(ToString::to_string).collect();
+        assert_eq!(months, ["2024-11", "2024-12", "2025-01", "2025-02"]);
+        assert_eq!(timeline.series.len(), 1);
+        assert_eq!(timeline.totals(), [1, 0, 1, 3]);
+
+        let timeline = Timeline::new(&repos, 1);
+        assert_eq!(
+            timeline.series,
+            [
+                TimelineSeries {
+                    name: "rust-lang/rust".to_string(),
+                    counts: vec![1, 0, 0, 2],
+                },
+                TimelineSeries {
+                    name: "Other".to_string(),
+                    counts: vec![0, 0, 1, 1],
+                },
+            ]
+        );
+        assert_eq!(timeline.totals(), [1, 0, 1, 3]);
+
+        assert_eq!(Timeline::new(&[], 3), Timeline::default());
+    }
+}
@@ -0,0 +1,321 @@
+//! Colour schemes of the SVG cards.
+
+/// The colours an [`SvgRenderer`](crate::render::SvgRenderer) draws with.
+///
+/// Parses from the name of a preset, see [`Theme::NAMES`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Theme {
+    pub background: String,
+    pub header_bg: String,
+    pub row_bg_even: String,
+    pub row_bg_odd: String,
+    pub total_row_bg: String,
+    pub text_color: String,
+    pub link_color: String,
+    pub star_color: String,
+    pub fork_color: String,
+    pub pr_color: String,
+    pub merged_color: String,
+    pub addition_color: String,
+    pub deletion_color: String,
+    pub issue_color: String,
+    pub review_color: String,
+    pub answer_color: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    /// The names of the presets.
+    pub const NAMES: [&str; 5] = ["light", "dark", "dracula", "solarized", "high_contrast"];
+
+    #[must_use]
+    pub fn light() -> Self {
+        Self::from_colors([
+            "#FFFFFF", // White
+            "#E8EEF6", // Soft cool header
+            "#FFFFFF", // White
+            "#F1F5F9", // Light cool gray
+            "#E2E8F0", // Cool gray for total
+            "#334155", // Slate gray
+            "#2563EB", // Bright blue for links
+            "#EAB308", // Yellow for stars
+            "#10B981", // Emerald for forks
+            "#2DA44E", // GitHub PR green
+            "#8250DF", // GitHub merged purple
+            "#1A7F37", // GitHub diff green
+            "#CF222E", // GitHub diff red
+            "#1A7F37", // GitHub open issue green
+            "#BF8700", // GitHub attention yellow
+            "#0969DA", // GitHub answered blue
+        ])
+    }
+
+    /// GitHub's dark default.
+    #[must_use]
+    pub fn dark() -> Self {
+        Self::from_colors([
+            "#0D1117", "#21262D", "#0D1117", "#161B22", "#30363D", "#C9D1D9", "#58A6FF", "#E3B341",
+            "#3FB950", "#3FB950", "#A371F7", "#3FB950", "#F85149", "#3FB950", "#D29922", "#58A6FF",
+        ])
+    }
+
+    #[must_use]
+    pub fn dracula() -> Self {
+        Self::from_colors([
+            "#282A36", "#44475A", "#282A36", "#2F3241", "#44475A", "#F8F8F2", "#8BE9FD", "#F1FA8C",
+            "#50FA7B", "#50FA7B", "#BD93F9", "#50FA7B", "#FF5555", "#50FA7B", "#FFB86C", "#8BE9FD",
+        ])
+    }
+
+    /// Solarized light.
+    #[must_use]
+    pub fn solarized() -> Self {
+        Self::from_colors([
+            "#FDF6E3", "#EEE8D5", "#FDF6E3", "#F5EFDC", "#EEE8D5", "#586E75", "#268BD2", "#B58900",
+            "#2AA198", "#859900", "#6C71C4", "#859900", "#DC322F", "#859900", "#CB4B16", "#268BD2",
+        ])
+    }
+
+    /// White on black, with GitHub's high contrast accents.
+    #[must_use]
+    pub fn high_contrast() -> Self {
+        Self::from_colors([
+            "#000000", "#272B33", "#000000", "#0E1116", "#272B33", "#FFFFFF", "#71B7FF", "#FFD700",
+            "#26CD4D", "#26CD4D", "#BE8FFF", "#26CD4D", "#FF6A69", "#26CD4D", "#F0B72F", "#71B7FF",
+        ])
+    }
+
+    /// Builds a theme from colours in the order of [`ThemeColor::ALL`].
+    fn from_colors(colors: [&str; 16]) -> Self {
+        let [
+            background,
+            header_bg,
+            row_bg_even,
+            row_bg_odd,
+            total_row_bg,
+            text_color,
+            link_color,
+            star_color,
+            fork_color,
+            pr_color,
+            merged_color,
+            addition_color,
+            deletion_color,
+            issue_color,
+            review_color,
+            answer_color,
+        ] = colors.map(str::to_string);
+        Self {
+            background,
+            header_bg,
+            row_bg_even,
+            row_bg_odd,
+            total_row_bg,
+            text_color,
+            link_color,
+            star_color,
+            fork_color,
+            pr_color,
+            merged_color,
+            addition_color,
+            deletion_color,
+            issue_color,
+            review_color,
+            answer_color,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, color: ThemeColor) -> &str {
+        match color {
+            ThemeColor::Background => &self.background,
+            ThemeColor::Header => &self.header_bg,
+            ThemeColor::Row => &self.row_bg_even,
+            ThemeColor::AltRow => &self.row_bg_odd,
+            ThemeColor::TotalRow => &self.total_row_bg,
+            ThemeColor::Text => &self.text_color,
+            ThemeColor::Link => &self.link_color,
+            ThemeColor::Star => &self.star_color,
+            ThemeColor::Fork => &self.fork_color,
+            ThemeColor::Pr => &self.pr_color,
+            ThemeColor::Merged => &self.merged_color,
+            ThemeColor::Addition => &self.addition_color,
+            ThemeColor::Deletion => &self.deletion_color,
+            ThemeColor::Issue => &self.issue_color,
+            ThemeColor::Review => &self.review_color,
+            ThemeColor::Answer => &self.answer_color,
+        }
+    }
+
+    /// Replaces the colour named `param`, see [`ThemeColor::as_str`], with `value`.
+    ///
+    /// `value` is a hex colour such as `#0D1117`, `0D1117` or `fff`.
+    pub fn with_color(mut self, param: &str, value: &str) -> Result<Self, String> {
+        let color = match param.parse()? {
+            ThemeColor::Background => &mut self.background,
+            ThemeColor::Header => &mut self.header_bg,
+            ThemeColor::Row => &mut self.row_bg_even,
+            ThemeColor::AltRow => &mut self.row_bg_odd,
+            ThemeColor::TotalRow => &mut self.total_row_bg,
+            ThemeColor::Text => &mut self.text_color,
+            ThemeColor::Link => &mut self.link_color,
+            ThemeColor::Star => &mut self.star_color,
+            ThemeColor::Fork => &mut self.fork_color,
+            ThemeColor::Pr => &mut self.pr_color,
+            ThemeColor::Merged => &mut self.merged_color,
+            ThemeColor::Addition => &mut self.addition_color,
+            ThemeColor::Deletion => &mut self.deletion_color,
+            ThemeColor::Issue => &mut self.issue_color,
+            ThemeColor::Review => &mut self.review_color,
+            ThemeColor::Answer => &mut self.answer_color,
+        };
+        *color = parse_hex_color(value).ok_or_else(|| {
+            format!("{param} '{value}' is not a hex colour such as #0D1117 or fff")
+        })?;
+        Ok(self)
+    }
+}
+
+/// What a colour of a [`Theme`] paints.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ThemeColor {
+    Background,
+    Header,
+    Row,
+    /// Every other row.
+    AltRow,
+    TotalRow,
+    Text,
+    Link,
+    Star,
+    Fork,
+    Pr,
+    Merged,
+    Addition,
+    Deletion,
+    Issue,
+    Review,
+    Answer,
+}
+
+impl ThemeColor {
+    pub const ALL: [ThemeColor; 16] = [
+        ThemeColor::Background,
+        ThemeColor::Header,
+        ThemeColor::Row,
+        ThemeColor::AltRow,
+        ThemeColor::TotalRow,
+        ThemeColor::Text,
+        ThemeColor::Link,
+        ThemeColor::Star,
+        ThemeColor::Fork,
+        ThemeColor::Pr,
+        ThemeColor::Merged,
+        ThemeColor::Addition,
+        ThemeColor::Deletion,
+        ThemeColor::Issue,
+        ThemeColor::Review,
+        ThemeColor::Answer,
+    ];
+
+    /// The name of the query param that overrides the colour, also its CSS class.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemeColor::Background => "bg_color",
+            ThemeColor::Header => "header_color",
+            ThemeColor::Row => "row_color",
+            ThemeColor::AltRow => "alt_row_color",
+            ThemeColor::TotalRow => "total_row_color",
+            ThemeColor::Text => "text_color",
+            ThemeColor::Link => "link_color",
+            ThemeColor::Star => "star_color",
+            ThemeColor::Fork => "fork_color",
+            ThemeColor::Pr => "pr_color",
+            ThemeColor::Merged => "merged_color",
+            ThemeColor::Addition => "addition_color",
+            ThemeColor::Deletion => "deletion_color",
+            ThemeColor::Issue => "issue_color",
+            ThemeColor::Review => "review_color",
+            ThemeColor::Answer => "answer_color",
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ThemeColor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|color| color.as_str() == s)
+            .ok_or_else(|| format!("unknown theme colour '{s}'"))
+    }
+}
+
+impl std::str::FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "light" | "default" => Ok(Self::light()),
+            "dark" => Ok(Self::dark()),
+            "dracula" => Ok(Self::dracula()),
+            "solarized" => Ok(Self::solarized()),
+            "high_contrast" => Ok(Self::high_contrast()),
+            _ => Err(format!(
+                "unknown theme '{s}', expected one of {}",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Normalizes a 3, 4, 6 or 8 digit hex colour, with or without `#`, to `#` and the digits.
+fn parse_hex_color(value: &str) -> Option<String> {
+    let digits = value.strip_prefix('#').unwrap_or(value);
+    (matches!(digits.len(), 3 | 4 | 6 | 8) && digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| format!("#{digits}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_parses_presets_and_custom_colors() {
+        for name in Theme::NAMES {
+            assert!(name.parse::<Theme>().is_ok(), "{name}");
+        }
+        assert_eq!("high-contrast".parse::<Theme>(), Ok(Theme::high_contrast()));
+        assert_eq!("Default".parse::<Theme>(), Ok(Theme::light()));
+        assert!("neon".parse::<Theme>().is_err());
+
+        let theme = Theme::dark()
+            .with_color("bg_color", "123456")
+            .and_then(|theme| theme.with_color("text_color", "#fff"))
+            .unwrap();
+        assert_eq!(theme.background, "#123456");
+        assert_eq!(theme.text_color, "#fff");
+        assert_eq!(theme.link_color, Theme::dark().link_color);
+
+        assert!(Theme::light().with_color("bg_color", "red").is_err());
+        assert!(
+            Theme::light()
+                .with_color("text_color", "#fff\" onload=\"x")
+                .is_err()
+        );
+        assert!(Theme::light().with_color("font", "#fff").is_err());
+    }
+}
@@ -7,19 +7,30 @@ use redis::AsyncCommands;
 use url::Url;
 use vercel_runtime::{Body, Error, Request, Response, StatusCode, run};
 
-use github_contrib_stats::github::{ContributedRepo, Repository};
-use github_contrib_stats::{github, render::Render, render::SvgRenderer};
+use github_contrib_stats::github::{
+    self, Affiliation, ContributedReposOptions, ContributionKind, CreatedReposOptions, DateRange,
+    GitHubClient, OwnerKind,
+};
+use github_contrib_stats::query::{RepoFilter, RepoOrder, StatsQuery};
+use github_contrib_stats::render::{Column, PrivateRepos, Render, SvgRenderer};
+use github_contrib_stats::stats::LanguageWeight;
+use github_contrib_stats::theme::{Theme, ThemeColor};
 
 const GENERATOR_TEMPLATE: &str = include_str!("generator.html");
 
 type Query<'a> = HashMap<Cow<'a, str>, Cow<'a, str>>;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 struct StatsParams {
-    max_repos: Option<usize>,
-    min_stars: u32,
-    min_forks: u32,
-    min_prs: u32,
+    query: StatsQuery,
+    private_repos: PrivateRepos,
+    theme: Theme,
+    dark_theme: Option<Theme>,
+    width: u32,
+    compact: bool,
+    columns: Vec<Column>,
+    created: CreatedReposOptions,
+    contributed: ContributedReposOptions,
 }
 
 #[tokio::main]
@@ -43,6 +54,42 @@ async fn main() -> Result<(), Error> {
                     None => not_found(),
                 }
             }
+            (path, method) if method == "GET" && path.ends_with("/languages.svg") => {
+                match username_from_svg_path(path, "/languages.svg") {
+                    Some(username) => render_languages_svg(username, &req).await,
+                    None => not_found(),
+                }
+            }
+            (path, method) if method == "GET" && path.ends_with("/timeline.svg") => {
+                match username_from_svg_path(path, "/timeline.svg") {
+                    Some(username) => render_timeline_svg(username, &req).await,
+                    None => not_found(),
+                }
+            }
+            (path, method) if method == "GET" && path.ends_with("/reviewed.svg") => {
+                match username_from_svg_path(path, "/reviewed.svg") {
+                    Some(username) => {
+                        render_contributions_svg(username, &req, Some(ContributionKind::Review))
+                            .await
+                    }
+                    None => not_found(),
+                }
+            }
+            (path, method) if method == "GET" && path.ends_with("/issues.svg") => {
+                match username_from_svg_path(path, "/issues.svg") {
+                    Some(username) => {
+                        render_contributions_svg(username, &req, Some(ContributionKind::Issue))
+                            .await
+                    }
+                    None => not_found(),
+                }
+            }
+            (path, method) if method == "GET" && path.ends_with("/contributions.svg") => {
+                match username_from_svg_path(path, "/contributions.svg") {
+                    Some(username) => render_contributions_svg(username, &req, None).await,
+                    None => not_found(),
+                }
+            }
             (path, method) if method == "GET" && username_from_page_path(path).is_some() => {
                 render_stats_page()
             }
@@ -51,7 +98,7 @@ async fn main() -> Result<(), Error> {
         match res {
             Ok(res) => Ok(res),
             Err(e) => Ok(Response::builder()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .status(error_status(&e))
                 .body(Body::from(format!("Error: {}", e)))?),
         }
     };
@@ -59,6 +106,13 @@ async fn main() -> Result<(), Error> {
     run(h).await
 }
 
+fn error_status(e: &Error) -> StatusCode {
+    e.downcast_ref::<github::Error>().map_or(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        github::Error::status_code,
+    )
+}
+
 fn not_found() -> Result<Response<Body>, Error> {
     Ok(Response::builder()
         .status(StatusCode::NOT_FOUND)
@@ -95,15 +149,192 @@ fn render_stats_page() -> Result<Response<Body>, Error> {
 fn parse_stats_params(req: &Request) -> Result<StatsParams, Error> {
     let url = Url::parse(&req.uri().to_string())?;
     let query: Query<'_> = url.query_pairs().collect();
+    let contributed = parse_contributed_options(&query)?;
+    let created = parse_created_options(&query, &contributed)?;
 
     Ok(StatsParams {
-        max_repos: parse_optional_usize(&query, "max_repos")?,
-        min_stars: parse_u32(&query, "min_stars")?,
-        min_forks: parse_u32(&query, "min_forks")?,
-        min_prs: parse_u32(&query, "min_prs")?,
+        query: parse_stats_query(&query)?,
+        private_repos: parse_private_repos(&query)?,
+        theme: parse_theme(&query)?,
+        dark_theme: parse_dark_theme(&query)?,
+        width: parse_width(&query)?,
+        compact: parse_bool(&query, "compact", false)?,
+        columns: parse_columns(&query)?,
+        created,
+        contributed,
     })
 }
 
+fn parse_include_private(query: &Query<'_>) -> Result<bool, Error> {
+    let include_private = parse_bool(query, "include_private", false)?;
+    // The service token may see private repos its owner would not want to publish.
+    if include_private && std::env::var("ALLOW_PRIVATE_REPOS").as_deref() != Ok("true") {
+        return Err(anyhow!("include_private is disabled on this deployment").into());
+    }
+    Ok(include_private)
+}
+
+fn parse_private_repos(query: &Query<'_>) -> Result<PrivateRepos, Error> {
+    query
+        .get("private_repos")
+        .filter(|value| !value.is_empty())
+        .map_or(Ok(PrivateRepos::default()), |value| {
+            value.parse().map_err(|e: String| anyhow!(e).into())
+        })
+}
+
+/// The `theme` preset, with the colours given by the params in [`ThemeColor::ALL`].
+fn parse_theme(query: &Query<'_>) -> Result<Theme, Error> {
+    let mut theme = match query.get("theme").filter(|value| !value.is_empty()) {
+        Some(value) if value == "auto" => Theme::default(),
+        Some(value) => value.parse().map_err(|e: String| anyhow!(e))?,
+        None => Theme::default(),
+    };
+    for color in ThemeColor::ALL {
+        let param = color.as_str();
+        if let Some(value) = query.get(param).filter(|value| !value.is_empty()) {
+            theme = theme.with_color(param, value).map_err(|e| anyhow!(e))?;
+        }
+    }
+    Ok(theme)
+}
+
+/// The `dark_theme` preset, or the dark one for `theme=auto`.
+fn parse_dark_theme(query: &Query<'_>) -> Result<Option<Theme>, Error> {
+    match query.get("dark_theme").filter(|value| !value.is_empty()) {
+        Some(value) => Ok(Some(value.parse().map_err(|e: String| anyhow!(e))?)),
+        None => Ok(query
+            .get("theme")
+            .is_some_and(|value| value == "auto")
+            .then(Theme::dark)),
+    }
+}
+
+/// The `columns` of the repository cards, each card's default ones when empty.
+fn parse_columns(query: &Query<'_>) -> Result<Vec<Column>, Error> {
+    match query.get("columns").filter(|value| !value.is_empty()) {
+        Some(value) => value
+            .split(',')
+            .map(|column| column.trim().parse().map_err(|e: String| anyhow!(e).into()))
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn parse_width(query: &Query<'_>) -> Result<u32, Error> {
+    let range = SvgRenderer::MIN_WIDTH..=SvgRenderer::MAX_WIDTH;
+    query.get("width").filter(|value| !value.is_empty()).map_or(
+        Ok(SvgRenderer::DEFAULT_WIDTH),
+        |value| {
+            value
+                .parse()
+                .ok()
+                .filter(|width| range.contains(width))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "width must be between {} and {}",
+                        range.start(),
+                        range.end()
+                    )
+                    .into()
+                })
+        },
+    )
+}
+
+fn parse_contributed_options(query: &Query<'_>) -> Result<ContributedReposOptions, Error> {
+    let defaults = ContributedReposOptions::default();
+
+    Ok(ContributedReposOptions {
+        range: parse_range(query)?,
+        include_private: parse_include_private(query)?,
+        include_archived: parse_bool(query, "include_archived", defaults.include_archived)?,
+    })
+}
+
+/// The created repos options, sharing the params that apply to both cards with `contributed`.
+fn parse_created_options(
+    query: &Query<'_>,
+    contributed: &ContributedReposOptions,
+) -> Result<CreatedReposOptions, Error> {
+    let defaults = CreatedReposOptions::default();
+
+    Ok(CreatedReposOptions {
+        owner: parse_owner(query)?,
+        affiliations: parse_affiliations(query)?,
+        include_forks: parse_bool(query, "include_forks", defaults.include_forks)?,
+        include_archived: contributed.include_archived,
+        include_unstarred: parse_bool(query, "include_unstarred", defaults.include_unstarred)?,
+        include_private: contributed.include_private,
+        range: contributed.range,
+    })
+}
+
+/// Which of the fetched repos a card lists, applied after the cache.
+fn parse_stats_query(query: &Query<'_>) -> Result<StatsQuery, Error> {
+    Ok(StatsQuery {
+        filter: parse_filter(query)?,
+        min_stars: parse_u32(query, "min_stars")?,
+        min_forks: parse_u32(query, "min_forks")?,
+        min_prs: parse_u32(query, "min_prs")?,
+        min_merged: parse_u32(query, "min_merged")?,
+        sort: parse_sort(query)?,
+        max_repos: parse_optional_usize(query, "max_repos")?,
+    })
+}
+
+fn parse_sort(query: &Query<'_>) -> Result<Option<RepoOrder>, Error> {
+    query
+        .get("sort")
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse().map_err(|e: String| anyhow!(e).into()))
+        .transpose()
+}
+
+fn created_cache_key(username: &str, options: &CreatedReposOptions) -> String {
+    let affiliations: Vec<_> = options.affiliations.iter().map(|a| a.as_str()).collect();
+    format!(
+        "created:{}:{}:{}:{}{}{}{}:{}:all",
+        username,
+        options.owner,
+        affiliations.join(","),
+        u8::from(options.include_forks),
+        u8::from(options.include_archived),
+        u8::from(options.include_unstarred),
+        u8::from(options.include_private),
+        options.range,
+    )
+}
+
+fn contributed_cache_key(name: &str, username: &str, options: &ContributedReposOptions) -> String {
+    format!(
+        "{}:{}:{}:{}{}:all",
+        name,
+        username,
+        options.range,
+        u8::from(options.include_private),
+        u8::from(options.include_archived),
+    )
+}
+
+fn parse_range(query: &Query<'_>) -> Result<DateRange, Error> {
+    let bound = |name| {
+        query
+            .get(name)
+            .map(|v| v.as_ref())
+            .filter(|v| !v.is_empty())
+    };
+    DateRange::parse(bound("since"), bound("until")).map_err(|e| anyhow!(e).into())
+}
+
+fn parse_filter(query: &Query<'_>) -> Result<RepoFilter, Error> {
+    RepoFilter::parse(
+        query.get("include").map(|v| v.as_ref()),
+        query.get("exclude").map(|v| v.as_ref()),
+    )
+    .map_err(|e| anyhow!(e).into())
+}
+
 fn parse_optional_usize(query: &Query<'_>, name: &str) -> Result<Option<usize>, Error> {
     query
         .get(name)
@@ -127,26 +358,69 @@ fn parse_u32(query: &Query<'_>, name: &str) -> Result<u32, Error> {
         })
 }
 
-fn filter_created_repos(mut repos: Vec<Repository>, params: StatsParams) -> Vec<Repository> {
-    repos.retain(|repo| {
-        repo.stargazer_count >= params.min_stars && repo.fork_count >= params.min_forks
-    });
-    if let Some(max_repos) = params.max_repos {
-        repos.truncate(max_repos);
+fn parse_bool(query: &Query<'_>, name: &str, default: bool) -> Result<bool, Error> {
+    query
+        .get(name)
+        .filter(|value| !value.is_empty())
+        .map_or(Ok(default), |value| match value.as_ref() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(anyhow!("{} must be true or false", name).into()),
+        })
+}
+
+fn parse_owner(query: &Query<'_>) -> Result<OwnerKind, Error> {
+    query
+        .get("owner")
+        .filter(|value| !value.is_empty())
+        .map_or(Ok(OwnerKind::Auto), |value| {
+            value.parse().map_err(|e: String| anyhow!(e).into())
+        })
+}
+
+fn parse_affiliations(query: &Query<'_>) -> Result<Vec<Affiliation>, Error> {
+    match query.get("affiliation").filter(|value| !value.is_empty()) {
+        Some(value) => value
+            .split(',')
+            .map(|affiliation| {
+                affiliation
+                    .trim()
+                    .parse()
+                    .map_err(|e: String| anyhow!(e).into())
+            })
+            .collect(),
+        None => Ok(vec![Affiliation::Owner]),
     }
-    repos
 }
 
-fn filter_contributed_repos(
-    mut repos: Vec<ContributedRepo>,
-    params: StatsParams,
-) -> Vec<ContributedRepo> {
-    repos
-        .retain(|repo| repo.stargazer_count >= params.min_stars && repo.pr_count >= params.min_prs);
-    if let Some(max_repos) = params.max_repos {
-        repos.truncate(max_repos);
+fn parse_kinds(req: &Request) -> Result<Vec<ContributionKind>, Error> {
+    let url = Url::parse(&req.uri().to_string())?;
+    let query: Query<'_> = url.query_pairs().collect();
+    match query.get("kinds").filter(|value| !value.is_empty()) {
+        Some(value) => value
+            .split(',')
+            .map(|kind| kind.trim().parse().map_err(|e: String| anyhow!(e).into()))
+            .collect(),
+        None => Ok(ContributionKind::ALL.to_vec()),
     }
-    repos
+}
+
+fn parse_language_weight(req: &Request) -> Result<LanguageWeight, Error> {
+    let url = Url::parse(&req.uri().to_string())?;
+    let query: Query<'_> = url.query_pairs().collect();
+    query
+        .get("weight")
+        .filter(|value| !value.is_empty())
+        .map_or(Ok(LanguageWeight::default()), |value| {
+            value.parse().map_err(|e: String| anyhow!(e).into())
+        })
+}
+
+/// How many repos the timeline stacks, none unless `stack` is given.
+fn parse_timeline_repos(req: &Request) -> Result<usize, Error> {
+    let url = Url::parse(&req.uri().to_string())?;
+    let query: Query<'_> = url.query_pairs().collect();
+    Ok(parse_optional_usize(&query, "stack")?.unwrap_or(0))
 }
 
 async fn get_redis_client() -> Result<redis::Client, Error> {
@@ -158,7 +432,7 @@ async fn get_redis_client() -> Result<redis::Client, Error> {
 async fn get_cached_or_compute<T, F>(cache_key: &str, compute: F) -> Result<T, Error>
 where
     T: serde::de::DeserializeOwned + serde::Serialize,
-    F: AsyncFnOnce() -> Result<T, anyhow::Error>,
+    F: AsyncFnOnce() -> Result<T, github::Error>,
 {
     let redis_client = get_redis_client().await?;
     let mut conn = match redis_client.get_multiplexed_tokio_connection().await {
@@ -205,14 +479,58 @@ where
 
 async fn render_created_svg(username: &str, req: &Request) -> Result<Response<Body>, Error> {
     let params = parse_stats_params(req)?;
+    let client = GitHubClient::from_env()?;
+
+    let cache_key = created_cache_key(username, &params.created);
+    let repos = get_cached_or_compute(&cache_key, || {
+        client.get_created_repos(username, &params.created)
+    })
+    .await?;
+    let repos = params.query.apply(repos);
+
+    let mut buf = String::new();
+    SvgRenderer::new()
+        .with_web_url(client.web_url())
+        .with_theme(params.theme.clone())
+        .with_dark_theme(params.dark_theme.clone())
+        .with_width(params.width)
+        .with_compact(params.compact)
+        .with_private_repos(params.private_repos)
+        .with_columns(params.columns.clone())
+        .render_created_repos(&mut buf, &repos, username);
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "image/svg+xml")
+        .header(
+            "Cache-Control",
+            "public, max-age=7200, s-maxage=7200, stale-while-revalidate=86400",
+        )
+        .header("ETag", format!("\"{}\"", username))
+        .body(buf.into())?)
+}
+
+/// Renders the languages of the created repos, sharing their cache with the created card.
+async fn render_languages_svg(username: &str, req: &Request) -> Result<Response<Body>, Error> {
+    let params = parse_stats_params(req)?;
+    let weight = parse_language_weight(req)?;
+    let client = GitHubClient::from_env()?;
 
-    let cache_key = format!("created:{}:all", username);
-    let repos =
-        get_cached_or_compute(&cache_key, || github::get_created_repos(&username, None)).await?;
-    let repos = filter_created_repos(repos, params);
+    let cache_key = created_cache_key(username, &params.created);
+    let repos = get_cached_or_compute(&cache_key, || {
+        client.get_created_repos(username, &params.created)
+    })
+    .await?;
+    let repos = params.query.apply(repos);
 
     let mut buf = String::new();
-    SvgRenderer::new().render_created_repos(&mut buf, &repos, &username);
+    SvgRenderer::new()
+        .with_web_url(client.web_url())
+        .with_theme(params.theme.clone())
+        .with_dark_theme(params.dark_theme.clone())
+        .with_width(params.width)
+        .with_compact(params.compact)
+        .render_languages(&mut buf, &repos, weight, username);
 
     Ok(Response::builder()
         .status(StatusCode::OK)
@@ -227,16 +545,107 @@ async fn render_created_svg(username: &str, req: &Request) -> Result<Response<Bo
 
 async fn render_contributed_svg(username: &str, req: &Request) -> Result<Response<Body>, Error> {
     let params = parse_stats_params(req)?;
+    let client = GitHubClient::from_env()?;
 
-    let cache_key = format!("contributed:{}:all", username);
+    let cache_key = contributed_cache_key("contributed", username, &params.contributed);
     let repos = get_cached_or_compute(&cache_key, || {
-        github::get_contributed_repos(&username, None)
+        client.get_contributed_repos(username, &params.contributed)
     })
     .await?;
-    let repos = filter_contributed_repos(repos, params);
+    let repos = params.query.apply(repos);
 
     let mut buf = String::new();
-    SvgRenderer::new().render_contributed_repos(&mut buf, &repos, &username);
+    SvgRenderer::new()
+        .with_web_url(client.web_url())
+        .with_theme(params.theme.clone())
+        .with_dark_theme(params.dark_theme.clone())
+        .with_width(params.width)
+        .with_compact(params.compact)
+        .with_private_repos(params.private_repos)
+        .with_columns(params.columns.clone())
+        .render_contributed_repos(&mut buf, &repos, username);
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "image/svg+xml")
+        .header(
+            "Cache-Control",
+            "public, max-age=7200, s-maxage=7200, stale-while-revalidate=86400",
+        )
+        .header("ETag", format!("\"{}\"", username))
+        .body(buf.into())?)
+}
+
+/// Renders the PRs per month of the contributed repos, sharing their cache with the contributed card.
+async fn render_timeline_svg(username: &str, req: &Request) -> Result<Response<Body>, Error> {
+    let params = parse_stats_params(req)?;
+    let top_repos = parse_timeline_repos(req)?;
+    let client = GitHubClient::from_env()?;
+
+    let cache_key = contributed_cache_key("contributed", username, &params.contributed);
+    let repos = get_cached_or_compute(&cache_key, || {
+        client.get_contributed_repos(username, &params.contributed)
+    })
+    .await?;
+    // Every PR of the matching repos counts, however many repos the other cards list.
+    let repos = params.query.filter_only().apply(repos);
+
+    let mut buf = String::new();
+    SvgRenderer::new()
+        .with_web_url(client.web_url())
+        .with_theme(params.theme.clone())
+        .with_dark_theme(params.dark_theme.clone())
+        .with_width(params.width)
+        .with_compact(params.compact)
+        .with_private_repos(params.private_repos)
+        .render_timeline(&mut buf, &repos, top_repos, username);
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "image/svg+xml")
+        .header(
+            "Cache-Control",
+            "public, max-age=7200, s-maxage=7200, stale-while-revalidate=86400",
+        )
+        .header("ETag", format!("\"{}\"", username))
+        .body(buf.into())?)
+}
+
+/// Renders the counts of the `kind` card, or of the kinds in the `kinds` query param.
+async fn render_contributions_svg(
+    username: &str,
+    req: &Request,
+    kind: Option<ContributionKind>,
+) -> Result<Response<Body>, Error> {
+    let params = parse_stats_params(req)?;
+    let kinds = match kind {
+        Some(kind) => vec![kind],
+        None => parse_kinds(req)?,
+    };
+    let client = GitHubClient::from_env()?;
+
+    let kind_names: Vec<_> = kinds.iter().map(|kind| kind.as_str()).collect();
+    let cache_key = contributed_cache_key(
+        &format!("contributions:{}", kind_names.join(",")),
+        username,
+        &params.contributed,
+    );
+    let repos = get_cached_or_compute(&cache_key, || {
+        client.get_contributions(username, &kinds, &params.contributed)
+    })
+    .await?;
+    let repos = params.query.apply(repos);
+
+    let mut buf = String::new();
+    SvgRenderer::new()
+        .with_web_url(client.web_url())
+        .with_theme(params.theme.clone())
+        .with_dark_theme(params.dark_theme.clone())
+        .with_width(params.width)
+        .with_compact(params.compact)
+        .with_private_repos(params.private_repos)
+        .with_columns(params.columns.clone())
+        .render_contributions(&mut buf, &repos, &kinds, username);
 
     Ok(Response::builder()
         .status(StatusCode::OK)
@@ -270,6 +679,18 @@ mod tests {
             username_from_svg_path("/j178/contributed.svg", "/contributed.svg"),
             Some("j178")
         );
+        assert_eq!(
+            username_from_svg_path("/j178/reviewed.svg", "/reviewed.svg"),
+            Some("j178")
+        );
+        assert_eq!(
+            username_from_svg_path("/j178/languages.svg", "/languages.svg"),
+            Some("j178")
+        );
+        assert_eq!(
+            username_from_svg_path("/j178/timeline.svg", "/timeline.svg"),
+            Some("j178")
+        );
         assert_eq!(username_from_svg_path("/j178", "/created.svg"), None);
     }
 }